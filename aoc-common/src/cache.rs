@@ -0,0 +1,180 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{default_input, AocError};
+
+pub const DEFAULT_YEAR: u32 = 2022;
+
+// downloaded puzzle inputs, stored as <dir>/<year>/<user>/dayNN.txt. inputs
+// differ between accounts, so user is a hash of the session token, which
+// keeps the token itself off the disk
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputCache {
+    dir: PathBuf,
+    year: u32,
+    user: String,
+}
+
+impl InputCache {
+    pub fn new(dir: impl Into<PathBuf>, year: u32, session: &str) -> Self {
+        InputCache {
+            dir: dir.into(),
+            year,
+            user: user_key(session),
+        }
+    }
+
+    // everything cached for one account and year
+    pub fn user_dir(&self) -> PathBuf {
+        self.dir.join(self.year.to_string()).join(&self.user)
+    }
+
+    pub fn path(&self, day: usize) -> PathBuf {
+        self.user_dir().join(format!("day{day:02}.txt"))
+    }
+
+    pub fn contains(&self, day: usize) -> bool {
+        self.path(day).is_file()
+    }
+
+    // written to a temporary file first so an interrupted download never
+    // leaves a truncated input behind
+    pub fn store(&self, day: usize, contents: &str) -> Result<PathBuf, AocError> {
+        let path = self.path(day);
+        let io = |err: std::io::Error| AocError::Io {
+            path: path.display().to_string(),
+            reason: err.to_string(),
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(io)?;
+        }
+        let partial = path.with_extension("part");
+        fs::write(&partial, contents).map_err(io)?;
+        fs::rename(&partial, &path).map_err(io)?;
+
+        Ok(path)
+    }
+}
+
+pub fn default_cache_dir() -> String {
+    env::var("AOC_CACHE_DIR")
+        .unwrap_or_else(|_| format!("{}/../.aoc-cache", env!("CARGO_MANIFEST_DIR")))
+}
+
+// the day's own input.txt when there is one, otherwise whatever `aoc fetch`
+// cached for it where AOC_CACHE_DIR and AOC_YEAR point. a cache fetched with
+// --cache or --year is only found through those variables
+pub fn find_input(day: usize) -> String {
+    let session = env::var("AOC_SESSION").ok();
+    let year = env::var("AOC_YEAR")
+        .ok()
+        .and_then(|y| y.parse().ok())
+        .unwrap_or(DEFAULT_YEAR);
+
+    let cached = find_cached(default_cache_dir(), year, session.as_deref(), day);
+    local_or_cached(day, cached)
+}
+
+// input.txt when it exists. when neither exists the input.txt path is
+// returned so the error names the file people expect
+pub fn local_or_cached(day: usize, cached: Option<PathBuf>) -> String {
+    let local = default_input(day);
+    match cached {
+        Some(path) if !Path::new(&local).is_file() => path.display().to_string(),
+        _ => local,
+    }
+}
+
+// a cached input for day. the session only picks the account, so reading
+// works without one as long as a single account has the day cached
+pub fn find_cached(
+    dir: impl Into<PathBuf>,
+    year: u32,
+    session: Option<&str>,
+    day: usize,
+) -> Option<PathBuf> {
+    let dir = dir.into();
+    if let Some(session) = session {
+        let cache = InputCache::new(dir, year, session);
+        return cache.contains(day).then(|| cache.path(day));
+    }
+
+    let file = format!("day{day:02}.txt");
+    let mut found = fs::read_dir(dir.join(year.to_string()))
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path().join(&file))
+        .filter(|path| path.is_file());
+    match (found.next(), found.next()) {
+        (Some(path), None) => Some(path),
+        _ => None,
+    }
+}
+
+// fnv-1a, which unlike DefaultHasher is stable between rust releases
+fn user_key(session: &str) -> String {
+    let hash = session.bytes().fold(0xcbf29ce484222325_u64, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths_are_keyed_by_year_and_user() {
+        let cache = InputCache::new("cache", 2022, "abc");
+
+        assert_eq!(
+            Path::new("cache/2022/e71fa2190541574b/day03.txt"),
+            cache.path(3)
+        );
+        assert_ne!(cache.path(3), InputCache::new("cache", 2022, "abd").path(3));
+        assert_ne!(cache.path(3), InputCache::new("cache", 2021, "abc").path(3));
+    }
+
+    #[test]
+    fn store_and_find() {
+        let dir = env::temp_dir().join(format!("aoc-cache-test-{}", std::process::id()));
+        let cache = InputCache::new(&dir, 2022, "abc");
+
+        assert!(!cache.contains(7));
+        let path = cache.store(7, "$ ls\n").unwrap_or_else(|err| {
+            println!("failed to unwrap test result, {err}");
+            PathBuf::new()
+        });
+        assert!(cache.contains(7));
+        assert_eq!("$ ls\n", fs::read_to_string(path).unwrap());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn find_cached_without_a_session() {
+        let dir = env::temp_dir().join(format!("aoc-find-test-{}", std::process::id()));
+        let (abc, abd) = (
+            InputCache::new(&dir, 2021, "abc"),
+            InputCache::new(&dir, 2021, "abd"),
+        );
+        abc.store(5, "a\n").unwrap();
+        abc.store(6, "a\n").unwrap();
+        abd.store(6, "b\n").unwrap();
+
+        assert_eq!(Some(abc.path(5)), find_cached(&dir, 2021, None, 5));
+        assert_eq!(Some(abd.path(6)), find_cached(&dir, 2021, Some("abd"), 6));
+        // two accounts have day 6 and neither is picked
+        assert_eq!(None, find_cached(&dir, 2021, None, 6));
+        assert_eq!(None, find_cached(&dir, 2022, None, 5));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn prefers_local_input() {
+        assert_eq!(default_input(1), find_input(1));
+    }
+}
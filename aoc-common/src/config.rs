@@ -0,0 +1,337 @@
+use std::error::Error;
+use std::str::FromStr;
+
+use crate::{find_input, read_input, Answer, AocError, Format, Record, Solution};
+
+// what every day binary takes on the command line:
+//   dayNN [--input PATH]... [--part 1|2] [--format text|json] [--help]
+//         [--NAME VALUE...] [PATH...]
+// the input defaults to input.txt in the day's crate and both parts are solved
+// unless --part picks one. each input is solved in turn, and - reads stdin
+#[derive(Debug, Default, PartialEq)]
+pub struct Config {
+    pub inputs: Vec<String>,
+    pub part: Option<i32>,
+    pub format: Format,
+    pub help: bool,
+    // any other --NAME VALUE pairs, for days with extra knobs. run rejects
+    // the ones the day does not list in Solution::OPTIONS
+    pub options: Vec<(String, String)>,
+}
+
+impl Config {
+    pub fn build(args: &[String]) -> Result<Config, &'static str> {
+        let mut config = Config::default();
+
+        let mut flags = args.iter().skip(1);
+        while let Some(flag) = flags.next() {
+            match flag.as_str() {
+                "--help" | "-h" => config.help = true,
+                "--input" => {
+                    let input = flags.next().ok_or("--input requires a value")?;
+                    config.inputs.push(input.clone());
+                }
+                "--part" => {
+                    let part = flags.next().ok_or("--part requires a value")?;
+                    let part = part.parse::<i32>().map_err(|_| "--part must be a number")?;
+                    if part != 1 && part != 2 {
+                        return Err("--part must be 1 or 2");
+                    }
+                    config.part = Some(part);
+                }
+                "--format" => {
+                    let format = flags.next().ok_or("--format requires a value")?;
+                    config.format = format.parse()?;
+                }
+                name if name.starts_with("--") => {
+                    let value = flags.next().ok_or("options require a value, see --help")?;
+                    config.options.push((name.to_string(), value.clone()));
+                }
+                "-" => config.inputs.push("-".to_string()),
+                name if name.starts_with('-') => return Err("unknown flag, see --help"),
+                path => config.inputs.push(path.to_string()),
+            }
+        }
+
+        Ok(config)
+    }
+
+    // the value of a day specific option, if it was given
+    pub fn option<T: FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+        match self.options.iter().rev().find(|(n, _)| n == name) {
+            Some((_, value)) => value
+                .parse()
+                .map(Some)
+                .map_err(|_| format!("{name} has an invalid value {value:?}")),
+            None => Ok(None),
+        }
+    }
+}
+
+fn usage<S: Solution>(day: usize) -> String {
+    let mut usage = format!("usage: day{day:02} [--part 1|2] [--format text|json]");
+    for (flag, _) in S::OPTIONS {
+        usage += &format!(" [{flag}]");
+    }
+    usage += " [PATH...]";
+
+    usage += "\n\n";
+    usage += "  PATH          puzzle inputs, - for stdin\n";
+    usage +=
+        &format!("                (default day{day:02}/input.txt, then the aoc fetch cache)\n");
+    usage += "                the cache is read from AOC_CACHE_DIR and AOC_YEAR, so\n";
+    usage += "                set those when fetching with --cache or --year\n";
+    usage += "  --input PATH  same as giving PATH\n";
+    usage += "  --part 1|2    only solve one part (default both)\n";
+    usage += "  --format F    text, or json for one object per part (default text)\n";
+    for (flag, help) in S::OPTIONS {
+        // long flags get their help on a line of its own
+        match flag.len() > 12 {
+            true => usage += &format!("  {flag}\n{:16}{help}\n", ""),
+            false => usage += &format!("  {flag:<12}  {help}\n"),
+        }
+    }
+    usage += "  --help        show this message\n";
+    usage
+}
+
+pub fn run<S: Solution>(day: usize, solution: &S, config: Config) -> Result<(), Box<dyn Error>> {
+    if !start::<S>(day, &config)? {
+        return Ok(());
+    }
+
+    let parts = match config.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    each_input(day, &config, |path| {
+        solve_input(solution, day, path, &parts, config.format)
+    })
+}
+
+// for days with modes that print a report on each input in place of the
+// answers. report gets the contents and returns the text to print
+pub fn report<S: Solution>(
+    day: usize,
+    config: &Config,
+    report: impl Fn(&str) -> Result<String, AocError>,
+) -> Result<(), Box<dyn Error>> {
+    if !start::<S>(day, config)? {
+        return Ok(());
+    }
+    if config.part.is_some() {
+        return Err("reports cover the whole input and take no --part".into());
+    }
+
+    each_input(day, config, |path| {
+        print!("{}", report(&read_input(path)?)?);
+        Ok(())
+    })
+}
+
+// what run and report both do first: --help prints the usage, and options
+// the day does not list are an error. false when there is nothing to run
+fn start<S: Solution>(day: usize, config: &Config) -> Result<bool, Box<dyn Error>> {
+    if config.help {
+        print!("{}", usage::<S>(day));
+        return Ok(false);
+    }
+
+    for (name, _) in &config.options {
+        if !S::OPTIONS
+            .iter()
+            .any(|(flag, _)| flag.split(' ').next() == Some(name))
+        {
+            return Err(format!("unknown option {name}, see --help").into());
+        }
+    }
+    Ok(true)
+}
+
+// calls f on every input, or on the cached one when none were given
+fn each_input(
+    day: usize,
+    config: &Config,
+    mut f: impl FnMut(&str) -> Result<(), Box<dyn Error>>,
+) -> Result<(), Box<dyn Error>> {
+    match config.inputs.as_slice() {
+        [] => return f(&find_input(day)),
+        [path] => return f(path),
+        _ => {}
+    }
+
+    // with several inputs one bad file is reported and the rest still run
+    let mut failed = 0;
+    for path in &config.inputs {
+        if config.format == Format::Text {
+            println!("==> {path} <==");
+        }
+        if let Err(err) = f(path) {
+            if config.format == Format::Text {
+                println!("error: {err}");
+            }
+            failed += 1;
+        }
+    }
+
+    if failed > 0 {
+        return Err(format!("{failed} of {} inputs failed", config.inputs.len()).into());
+    }
+    Ok(())
+}
+
+fn solve_input<S: Solution>(
+    solution: &S,
+    day: usize,
+    path: &str,
+    parts: &[i32],
+    format: Format,
+) -> Result<(), Box<dyn Error>> {
+    if format == Format::Json {
+        return solve_json(solution, day, path, parts);
+    }
+
+    let contents = read_input(path)?;
+    for part in parts {
+        let result = solution.solve(&contents, *part)?;
+        match result {
+            Answer::Image(_) => println!("part {part} ->\n{result}"),
+            _ => println!("part {part} -> {result}"),
+        }
+    }
+    Ok(())
+}
+
+// every part gets a record, even when the input could not be read, so that
+// the output is always one json object per line
+fn solve_json<S: Solution>(
+    solution: &S,
+    day: usize,
+    path: &str,
+    parts: &[i32],
+) -> Result<(), Box<dyn Error>> {
+    let contents = read_input(path);
+    let mut failed = false;
+    for part in parts {
+        let record = Record::timed(day, *part, path, || {
+            contents
+                .clone()
+                .and_then(|contents| solution.solve(&contents, *part))
+        });
+        failed |= record.answer.is_err();
+        println!("{}", record.to_json());
+    }
+
+    match failed {
+        true => Err(format!("could not solve {path}").into()),
+        false => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Knobs;
+
+    impl Solution for Knobs {
+        type Input = ();
+
+        const OPTIONS: &'static [(&'static str, &'static str)] = &[("--top N", "how many")];
+
+        fn parse(&self, _: &str) -> Result<Self::Input, AocError> {
+            Ok(())
+        }
+
+        fn part1(&self, _: &Self::Input) -> Result<Answer, AocError> {
+            Ok(Answer::Int(1))
+        }
+
+        fn part2(&self, _: &Self::Input) -> Result<Answer, AocError> {
+            Ok(Answer::Int(2))
+        }
+    }
+
+    fn args(raw: &str) -> Vec<String> {
+        raw.split(' ').map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn build() {
+        assert_eq!(
+            Config {
+                inputs: vec!["input.txt".to_string()],
+                part: Some(2),
+                format: Format::Json,
+                help: false,
+                options: vec![("--top".to_string(), "5".to_string())],
+            },
+            Config::build(&args(
+                "day01 --input input.txt --part 2 --format json --top 5"
+            ))
+            .unwrap()
+        );
+        assert_eq!(Config::default(), Config::build(&args("day01")).unwrap());
+        assert!(Config::build(&args("day01 --help")).unwrap().help);
+        assert_eq!(
+            vec!["a.txt", "-", "b.txt"],
+            Config::build(&args("day01 a.txt - --input b.txt"))
+                .unwrap()
+                .inputs
+        );
+    }
+
+    #[test]
+    fn build_rejects_bad_values() {
+        assert!(Config::build(&args("day01 --part two")).is_err());
+        assert!(Config::build(&args("day01 --part 3")).is_err());
+        assert!(Config::build(&args("day01 --input")).is_err());
+        assert!(Config::build(&args("day01 --top")).is_err());
+        assert!(Config::build(&args("day01 --format yaml")).is_err());
+        assert!(Config::build(&args("day01 input.txt 2 -p")).is_err());
+    }
+
+    #[test]
+    fn options() {
+        let config = Config::build(&args("day01 --top 5 --name x")).unwrap();
+
+        assert_eq!(Ok(Some(5)), config.option::<i32>("--top"));
+        assert_eq!(Ok(None), config.option::<i32>("--bottom"));
+        assert!(config.option::<i32>("--name").is_err());
+    }
+
+    #[test]
+    fn help_and_unknown_options() {
+        let usage = usage::<Knobs>(1);
+
+        assert!(usage
+            .starts_with("usage: day01 [--part 1|2] [--format text|json] [--top N] [PATH...]\n"));
+        assert!(usage.contains("  --top N       how many\n"));
+
+        let config = Config::build(&args("day01 --bottom 2")).unwrap();
+        assert_eq!(
+            "unknown option --bottom, see --help",
+            run(1, &Knobs, config).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn reports_check_options_and_inputs() {
+        let never = |_: &str| -> Result<String, AocError> { panic!("report ran") };
+
+        let config = Config::build(&args("day01 --top 3 --bottom 2")).unwrap();
+        assert_eq!(
+            "unknown option --bottom, see --help",
+            report::<Knobs>(1, &config, never).unwrap_err().to_string()
+        );
+        let config = Config::build(&args("day01 --top 3 --help")).unwrap();
+        assert!(report::<Knobs>(1, &config, never).is_ok());
+
+        let config = Config::build(&args("day01 missing-a.txt missing-b.txt")).unwrap();
+        assert_eq!(
+            "2 of 2 inputs failed",
+            report::<Knobs>(1, &config, never).unwrap_err().to_string()
+        );
+    }
+}
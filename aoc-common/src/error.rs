@@ -0,0 +1,155 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AocError {
+    Parse {
+        day: usize,
+        line: usize,
+        column: usize,
+        text: String,
+        reason: String,
+    },
+    Io {
+        path: String,
+        reason: String,
+    },
+    UnsupportedPart(i32),
+    Unsolvable {
+        day: usize,
+        reason: String,
+    },
+}
+
+impl AocError {
+    pub fn unsolvable(day: usize, reason: impl Into<String>) -> Self {
+        AocError::Unsolvable {
+            day,
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AocError::Parse {
+                day,
+                line,
+                column,
+                text,
+                reason,
+            } => write!(
+                f,
+                "day {day:02}, line {line}, column {column}: {reason} (found {text:?})"
+            ),
+            AocError::Io { path, reason } => write!(f, "could not read {path}: {reason}"),
+            AocError::UnsupportedPart(part) => {
+                write!(
+                    f,
+                    "part {part} is not supported, must select problem 1 or 2"
+                )
+            }
+            AocError::Unsolvable { day, reason } => write!(f, "day {day:02}: {reason}"),
+        }
+    }
+}
+
+impl Error for AocError {}
+
+// a line of puzzle input along with where it sits in the file, so parsers can
+// point at the exact text that tripped them up
+#[derive(Debug, Clone, Copy)]
+pub struct InputLine<'a> {
+    pub day: usize,
+    pub line: usize,
+    pub text: &'a str,
+}
+
+impl<'a> InputLine<'a> {
+    pub fn new(day: usize, line: usize, text: &'a str) -> Self {
+        InputLine { day, line, text }
+    }
+
+    pub fn error(&self, token: &str, reason: impl Into<String>) -> AocError {
+        AocError::Parse {
+            day: self.day,
+            line: self.line,
+            column: self.column(token),
+            text: token.to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    pub fn parse_num<T: FromStr>(&self, token: &str) -> Result<T, AocError> {
+        let token = token.trim();
+        token
+            .parse::<T>()
+            .map_err(|_| self.error(token, "expected a number"))
+    }
+
+    // 1-based column of token when it was sliced out of this line, otherwise
+    // the start of the line
+    pub fn column(&self, token: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let at = token.as_ptr() as usize;
+
+        if at >= start && at + token.len() <= start + self.text.len() {
+            at - start + 1
+        } else {
+            1
+        }
+    }
+}
+
+pub fn input_lines(day: usize, contents: &str) -> impl Iterator<Item = InputLine<'_>> {
+    contents
+        .lines()
+        .enumerate()
+        .map(move |(i, text)| InputLine::new(day, i + 1, text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_at_token() {
+        let line = input_lines(4, "2-4,6-8\n2-x,4-5").nth(1).unwrap();
+        let token = line.text.split(['-', ',']).nth(1).unwrap();
+
+        assert_eq!(
+            AocError::Parse {
+                day: 4,
+                line: 2,
+                column: 3,
+                text: "x".to_string(),
+                reason: "expected a number".to_string(),
+            },
+            line.parse_num::<i32>(token).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn display() {
+        let line = InputLine::new(2, 7, "A Q");
+        let err = line.error(&line.text[2..], "unknown move");
+
+        assert_eq!(
+            "day 02, line 7, column 3: unknown move (found \"Q\")",
+            err.to_string()
+        );
+        assert_eq!(
+            "day 06: no packet start index found",
+            AocError::unsolvable(6, "no packet start index found").to_string()
+        );
+    }
+
+    #[test]
+    fn foreign_token_column() {
+        let line = InputLine::new(1, 1, "abc");
+
+        assert_eq!(1, line.column("zzz"));
+    }
+}
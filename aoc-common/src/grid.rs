@@ -0,0 +1,319 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::{input_lines, AocError, Point, Vector};
+
+// a rectangular grid stored row by row. cells are addressed by Point, with x
+// the column and y the row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    // None when the rows are not all the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map_or(0, |r| r.len());
+        if rows.iter().any(|r| r.len() != width) {
+            return None;
+        }
+
+        let height = rows.len();
+        Some(Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    // one row per line and one cell per char. leading and trailing whitespace
+    // is ignored, and cell decides what each char means (None rejects it)
+    pub fn parse(
+        day: usize,
+        contents: &str,
+        reason: &str,
+        cell: impl Fn(char) -> Option<T>,
+    ) -> Result<Self, AocError> {
+        let mut width = None;
+        let mut cells = Vec::new();
+        let mut height = 0;
+
+        for line in input_lines(day, contents) {
+            let text = line.text.trim();
+            for (i, c) in text.char_indices() {
+                match cell(c) {
+                    Some(value) => cells.push(value),
+                    None => return Err(line.error(&text[i..i + c.len_utf8()], reason)),
+                }
+            }
+
+            let len = text.chars().count();
+            if *width.get_or_insert(len) != len {
+                return Err(line.error(text, "every row of the grid must be the same length"));
+            }
+            height += 1;
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Grid {
+                width,
+                height,
+                cells,
+            }),
+            _ => Err(AocError::unsolvable(day, "grid is empty")),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.x >= 0 && p.y >= 0 && (p.x as usize) < self.width && (p.y as usize) < self.height
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        if self.contains(p) {
+            Some(&self.cells[p.y as usize * self.width + p.x as usize])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        if self.contains(p) {
+            Some(&mut self.cells[p.y as usize * self.width + p.x as usize])
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    // every point in reading order
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.height)
+            .flat_map(move |y| (0..self.width).map(move |x| Point::new(x as i64, y as i64)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> + '_ {
+        self.points().zip(self.cells.iter())
+    }
+
+    // first point whose cell matches, in reading order
+    pub fn find(&self, pred: impl Fn(&T) -> bool) -> Option<Point> {
+        self.iter().find(|(_, c)| pred(c)).map(|(p, _)| p)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn col(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        self.cells
+            .iter()
+            .skip(x)
+            .step_by(self.width.max(1))
+            .take(self.height)
+    }
+
+    pub fn cols(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(move |x| self.col(x))
+    }
+
+    // the up/down/left/right neighbours of p that are on the grid
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        Vector::ORTHOGONAL
+            .into_iter()
+            .map(move |v| p + v)
+            .filter(move |n| self.contains(*n))
+    }
+
+    // like neighbours4 but including the diagonals
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        Vector::ALL
+            .into_iter()
+            .map(move |v| p + v)
+            .filter(move |n| self.contains(*n))
+    }
+
+    // walks from (but not including) p in steps of step until it leaves the
+    // grid
+    pub fn ray(&self, p: Point, step: Vector) -> impl Iterator<Item = (Point, &T)> + '_ {
+        std::iter::successors(Some(p + step), move |n| Some(*n + step))
+            .map_while(move |n| self.get(n).map(|c| (n, c)))
+    }
+
+    pub fn render(&self, cell: impl Fn(&T) -> char) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            out.extend(row.iter().map(&cell));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    // rows become columns
+    pub fn transpose(&self) -> Grid<T> {
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: self.cols().flatten().cloned().collect(),
+        }
+    }
+
+    // a quarter turn clockwise
+    pub fn rotate_cw(&self) -> Grid<T> {
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: self
+                .cols()
+                .flat_map(|c| c.collect::<Vec<&T>>().into_iter().rev())
+                .cloned()
+                .collect(),
+        }
+    }
+
+    // a quarter turn anticlockwise
+    pub fn rotate_ccw(&self) -> Grid<T> {
+        let cols: Vec<Vec<&T>> = self.cols().map(|c| c.collect()).collect();
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: cols.into_iter().rev().flatten().cloned().collect(),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.get(p).expect("point outside of grid")
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        self.get_mut(p).expect("point outside of grid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(contents: &str) -> Result<Grid<u32>, AocError> {
+        Grid::parse(8, contents, "expected a digit", |c| c.to_digit(10))
+    }
+
+    #[test]
+    fn parse_and_index() {
+        let grid = digits("123\n456").unwrap();
+
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!(6, grid[Point::new(2, 1)]);
+        assert_eq!(&[4, 5, 6], grid.row(1));
+        assert_eq!(None, grid.get(Point::new(3, 0)));
+        assert_eq!(None, grid.get(Point::new(0, -1)));
+        assert_eq!(Some(Point::new(1, 1)), grid.find(|c| *c == 5));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            "day 08, line 2, column 2: expected a digit (found \"x\")",
+            digits("123\n4x6").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "day 08, line 2, column 1: every row of the grid must be the same length (found \"45\")",
+            digits("123\n45").unwrap_err().to_string()
+        );
+        assert!(digits("").is_err());
+    }
+
+    #[test]
+    fn ragged_rows() {
+        assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_none());
+        assert_eq!(
+            Grid::new(2, 1, 0),
+            Grid::from_rows(vec![vec![0, 0]]).unwrap()
+        );
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = Grid::new(3, 3, '.');
+        let mut corner: Vec<Point> = grid.neighbours4(Point::new(0, 0)).collect();
+        corner.sort();
+
+        assert_eq!(vec![Point::new(0, 1), Point::new(1, 0)], corner);
+        assert_eq!(4, grid.neighbours4(Point::new(1, 1)).count());
+        assert_eq!(3, grid.neighbours8(Point::new(2, 2)).count());
+        assert_eq!(8, grid.neighbours8(Point::new(1, 1)).count());
+    }
+
+    #[test]
+    fn rows_cols_and_rays() {
+        let grid = digits("123\n456").unwrap();
+
+        assert_eq!(
+            vec![&[1, 2, 3][..], &[4, 5, 6][..]],
+            grid.rows().collect::<Vec<_>>()
+        );
+        assert_eq!(vec![&2, &5], grid.col(1).collect::<Vec<_>>());
+        assert_eq!(3, grid.cols().count());
+        assert_eq!(
+            vec![(Point::new(1, 1), &5), (Point::new(0, 1), &4)],
+            grid.ray(Point::new(2, 1), Vector::LEFT).collect::<Vec<_>>()
+        );
+        assert_eq!(0, grid.ray(Point::new(2, 1), Vector::DOWN).count());
+    }
+
+    #[test]
+    fn transform_and_render() {
+        let grid = digits("123\n456").unwrap();
+
+        assert_eq!("14\n25\n36\n", grid.transpose().to_string());
+        assert_eq!("41\n52\n63\n", grid.rotate_cw().to_string());
+        assert_eq!("36\n25\n14\n", grid.rotate_ccw().to_string());
+        assert_eq!(grid, grid.rotate_cw().rotate_ccw());
+        assert_eq!(
+            "#..\n###\n",
+            grid.render(|c| if *c == 1 || *c > 3 { '#' } else { '.' })
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use std::fs;
+use std::io::{self, Read};
+
+use crate::AocError;
+
+// puzzle inputs are read whole, with windows line endings normalised so the
+// parsers only ever see '\n'. a path of - reads stdin instead
+pub fn read_input(path: &str) -> Result<String, AocError> {
+    let contents = if path == "-" {
+        let mut contents = String::new();
+        io::stdin().read_to_string(&mut contents).map(|_| contents)
+    } else {
+        fs::read_to_string(path)
+    }
+    .map_err(|err| AocError::Io {
+        path: path.to_string(),
+        reason: err.to_string(),
+    })?;
+
+    Ok(contents.replace("\r\n", "\n"))
+}
+
+// each day crate keeps its puzzle input next to its Cargo.toml
+pub fn default_input(day: usize) -> String {
+    format!("{}/../day{:02}/input.txt", env!("CARGO_MANIFEST_DIR"), day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_file() {
+        let err = read_input("no/such/input.txt").unwrap_err();
+
+        assert!(matches!(err, AocError::Io { .. }));
+        assert!(err
+            .to_string()
+            .starts_with("could not read no/such/input.txt"));
+    }
+
+    #[test]
+    fn default_paths() {
+        assert!(default_input(3).ends_with("/../day03/input.txt"));
+        assert!(default_input(15).ends_with("/../day15/input.txt"));
+    }
+}
@@ -0,0 +1,19 @@
+mod cache;
+mod config;
+mod error;
+mod grid;
+mod input;
+mod point;
+mod record;
+mod solution;
+
+pub use cache::{
+    default_cache_dir, find_cached, find_input, local_or_cached, InputCache, DEFAULT_YEAR,
+};
+pub use config::{report, run, Config};
+pub use error::{input_lines, AocError, InputLine};
+pub use grid::Grid;
+pub use input::{default_input, read_input};
+pub use point::{Point, Vector};
+pub use record::{Format, Record};
+pub use solution::{Answer, Solution};
@@ -0,0 +1,121 @@
+use std::ops::{Add, AddAssign, Mul, Sub};
+
+// a position on a 2D plane. grids put (0, 0) in the top left with y growing
+// downwards, so UP is negative y
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+// the difference between two points, e.g. one step of movement
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Vector {
+    pub dx: i64,
+    pub dy: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub fn new(x: i64, y: i64) -> Self {
+        Point { x, y }
+    }
+
+    pub fn manhattan(self, other: Point) -> i64 {
+        (self - other).manhattan()
+    }
+}
+
+impl Vector {
+    pub const UP: Vector = Vector { dx: 0, dy: -1 };
+    pub const DOWN: Vector = Vector { dx: 0, dy: 1 };
+    pub const LEFT: Vector = Vector { dx: -1, dy: 0 };
+    pub const RIGHT: Vector = Vector { dx: 1, dy: 0 };
+
+    pub const ORTHOGONAL: [Vector; 4] = [Vector::UP, Vector::RIGHT, Vector::DOWN, Vector::LEFT];
+    pub const ALL: [Vector; 8] = [
+        Vector::UP,
+        Vector { dx: 1, dy: -1 },
+        Vector::RIGHT,
+        Vector { dx: 1, dy: 1 },
+        Vector::DOWN,
+        Vector { dx: -1, dy: 1 },
+        Vector::LEFT,
+        Vector { dx: -1, dy: -1 },
+    ];
+
+    pub fn new(dx: i64, dy: i64) -> Self {
+        Vector { dx, dy }
+    }
+
+    pub fn manhattan(self) -> i64 {
+        self.dx.abs() + self.dy.abs()
+    }
+
+    // number of king moves needed to cover the vector
+    pub fn chebyshev(self) -> i64 {
+        self.dx.abs().max(self.dy.abs())
+    }
+
+    // the same direction, but at most one step along each axis
+    pub fn signum(self) -> Vector {
+        Vector::new(self.dx.signum(), self.dy.signum())
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, v: Vector) -> Point {
+        Point::new(self.x + v.dx, self.y + v.dy)
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, v: Vector) {
+        self.x += v.dx;
+        self.y += v.dy;
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, other: Point) -> Vector {
+        Vector::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, other: Vector) -> Vector {
+        Vector::new(self.dx + other.dx, self.dy + other.dy)
+    }
+}
+
+impl Mul<i64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, n: i64) -> Vector {
+        Vector::new(self.dx * n, self.dy * n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let mut p = Point::new(2, 3);
+        p += Vector::UP * 2;
+
+        assert_eq!(Point::new(2, 1), p);
+        assert_eq!(Vector::new(3, -4), Point::new(5, -1) - Point::new(2, 3));
+        assert_eq!(7, Point::new(5, -1).manhattan(Point::new(2, 3)));
+        assert_eq!(4, Vector::new(3, -4).chebyshev());
+        assert_eq!(Vector::new(1, -1), Vector::new(3, -4).signum());
+    }
+}
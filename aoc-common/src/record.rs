@@ -0,0 +1,135 @@
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use serde::ser::{Serialize, Serializer};
+
+use crate::{Answer, AocError};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    // one json object per solved part, one per line
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err("--format must be text or json"),
+        }
+    }
+}
+
+// the outcome of solving one part of one input, and how long it took
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub day: usize,
+    pub part: i32,
+    pub input: String,
+    pub answer: Result<Answer, AocError>,
+    pub elapsed: Duration,
+}
+
+#[derive(serde::Serialize)]
+struct Json<'a> {
+    day: usize,
+    part: i32,
+    input: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    answer: Option<&'a Answer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    answer_type: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    elapsed_ms: f64,
+}
+
+impl Record {
+    pub fn timed(
+        day: usize,
+        part: i32,
+        input: &str,
+        solve: impl FnOnce() -> Result<Answer, AocError>,
+    ) -> Record {
+        let start = Instant::now();
+        let answer = solve();
+
+        Record {
+            day,
+            part,
+            input: input.to_string(),
+            answer,
+            elapsed: start.elapsed(),
+        }
+    }
+
+    // failed parts carry an error message in place of answer and answer_type
+    pub fn to_json(&self) -> String {
+        let json = Json {
+            day: self.day,
+            part: self.part,
+            input: &self.input,
+            answer: self.answer.as_ref().ok(),
+            answer_type: self.answer.as_ref().ok().map(Answer::kind),
+            error: self.answer.as_ref().err().map(|err| err.to_string()),
+            elapsed_ms: self.elapsed.as_secs_f64() * 1000.0,
+        };
+
+        serde_json::to_string(&json).expect("records always serialize")
+    }
+}
+
+// numbers stay numbers and images become an array of rows
+impl Serialize for Answer {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        match self {
+            Answer::Int(n) => serializer.serialize_i64(*n),
+            Answer::BigInt(n) => serializer.serialize_i128(*n),
+            Answer::Text(s) => serializer.serialize_str(s),
+            Answer::Image(rows) => rows.serialize(serializer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(answer: Result<Answer, AocError>) -> Record {
+        Record {
+            day: 10,
+            part: 2,
+            input: "input.txt".to_string(),
+            answer,
+            elapsed: Duration::from_micros(1500),
+        }
+    }
+
+    #[test]
+    fn json_answers() {
+        assert_eq!(
+            r##"{"day":10,"part":2,"input":"input.txt","answer":["#.",".#"],"answer_type":"image","elapsed_ms":1.5}"##,
+            record(Ok(Answer::Image(vec!["#.".to_string(), ".#".to_string()]))).to_json()
+        );
+        assert_eq!(
+            r#"{"day":10,"part":2,"input":"input.txt","answer":170141183460469231731687303715884105727,"answer_type":"bigint","elapsed_ms":1.5}"#,
+            record(Ok(Answer::BigInt(i128::MAX))).to_json()
+        );
+        assert_eq!(
+            r#"{"day":10,"part":2,"input":"input.txt","error":"day 10: no signal","elapsed_ms":1.5}"#,
+            record(Err(AocError::unsolvable(10, "no signal"))).to_json()
+        );
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!(Ok(Format::Json), "json".parse());
+        assert_eq!(Ok(Format::Text), "text".parse());
+        assert!("yaml".parse::<Format>().is_err());
+    }
+}
@@ -0,0 +1,134 @@
+use std::fmt;
+
+use crate::AocError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    BigInt(i128),
+    Text(String),
+    // rows of ascii art, e.g. the day10 crt screen
+    Image(Vec<String>),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::BigInt(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+            Answer::Image(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+impl Answer {
+    // how --format json labels the answer
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Int(_) => "int",
+            Answer::BigInt(_) => "bigint",
+            Answer::Text(_) => "text",
+            Answer::Image(_) => "image",
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(n: i32) -> Self {
+        Answer::Int(n as i64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Int(n)
+    }
+}
+
+// anything past i64::MAX is kept exact as a BigInt
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        i64::try_from(n).map_or(Answer::BigInt(n as i128), Answer::Int)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Int(n as i64)
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(n: i128) -> Self {
+        Answer::BigInt(n)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+pub trait Solution {
+    type Input;
+
+    // extra --NAME VALUE flags the day binary accepts, as ("--name VALUE",
+    // help) pairs. the values are read from Config::option
+    const OPTIONS: &'static [(&'static str, &'static str)] = &[];
+
+    fn parse(&self, contents: &str) -> Result<Self::Input, AocError>;
+    fn part1(&self, input: &Self::Input) -> Result<Answer, AocError>;
+    fn part2(&self, input: &Self::Input) -> Result<Answer, AocError>;
+
+    fn solve(&self, contents: &str, problem_number: i32) -> Result<Answer, AocError> {
+        if problem_number != 1 && problem_number != 2 {
+            return Err(AocError::UnsupportedPart(problem_number));
+        }
+
+        let input = self.parse(contents)?;
+        if problem_number == 1 {
+            self.part1(&input)
+        } else {
+            self.part2(&input)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Lines;
+
+    impl Solution for Lines {
+        type Input = Vec<String>;
+
+        fn parse(&self, contents: &str) -> Result<Self::Input, AocError> {
+            Ok(contents.lines().map(|l| l.to_string()).collect())
+        }
+
+        fn part1(&self, input: &Self::Input) -> Result<Answer, AocError> {
+            Ok(input.len().into())
+        }
+
+        fn part2(&self, input: &Self::Input) -> Result<Answer, AocError> {
+            Ok(Answer::Image(input.clone()))
+        }
+    }
+
+    #[test]
+    fn dispatches_parts() {
+        assert_eq!(Answer::Int(2), Lines.solve("#.\n.#", 1).unwrap());
+        assert_eq!("#.\n.#", Lines.solve("#.\n.#", 2).unwrap().to_string());
+    }
+
+    #[test]
+    fn unsupported_part() {
+        assert_eq!(
+            AocError::UnsupportedPart(3),
+            Lines.solve("#.\n.#", 3).unwrap_err()
+        );
+    }
+}
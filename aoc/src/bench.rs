@@ -0,0 +1,249 @@
+use std::error::Error;
+use std::fs;
+use std::time::Duration;
+
+use aoc_common::{find_input, read_input};
+use serde::Serialize;
+
+use crate::registry;
+
+const DEFAULT_RUNS: usize = 10;
+
+#[derive(Debug, PartialEq)]
+pub struct Config {
+    day: Option<usize>,
+    runs: usize,
+    json: Option<String>,
+}
+
+impl Config {
+    // flags after `aoc bench`. without --day every registered day is timed
+    pub fn build(args: &[String]) -> Result<Config, &'static str> {
+        let mut config = Config {
+            day: None,
+            runs: DEFAULT_RUNS,
+            json: None,
+        };
+
+        let mut flags = args.iter();
+        while let Some(flag) = flags.next() {
+            match flag.as_str() {
+                "--day" => {
+                    let day = flags.next().ok_or("--day requires a value")?;
+                    let day = day.parse::<usize>().map_err(|_| "--day must be a number")?;
+                    if registry::find(day).is_none() {
+                        return Err("--day does not match a registered day");
+                    }
+                    config.day = Some(day);
+                }
+                "--runs" => {
+                    let runs = flags.next().ok_or("--runs requires a value")?;
+                    let runs = runs
+                        .parse::<usize>()
+                        .map_err(|_| "--runs must be a number")?;
+                    if runs == 0 {
+                        return Err("--runs must be at least 1");
+                    }
+                    config.runs = runs;
+                }
+                "--json" => {
+                    let path = flags.next().ok_or("--json requires a value")?;
+                    config.json = Some(path.clone());
+                }
+                _ => return Err(crate::USAGE),
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+// how long each phase of one day took over every run, in milliseconds
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Measurement {
+    day: usize,
+    phase: &'static str,
+    runs: usize,
+    mean_ms: f64,
+    stddev_ms: f64,
+    min_ms: f64,
+}
+
+#[derive(Debug, Serialize)]
+struct Report {
+    runs: usize,
+    results: Vec<Measurement>,
+}
+
+pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
+    let days: Vec<&registry::Day> = match config.day {
+        Some(number) => vec![registry::find(number).ok_or("unknown day")?],
+        None => registry::DAYS.iter().collect(),
+    };
+
+    let mut results = Vec::new();
+    for day in days {
+        let contents = read_input(&find_input(day.number))?;
+        results.extend(bench_day(day, &contents, config.runs)?);
+    }
+
+    print!("{}", bench_table(&results));
+
+    if let Some(path) = config.json {
+        let report = Report {
+            runs: config.runs,
+            results,
+        };
+        fs::write(&path, serde_json::to_string_pretty(&report)? + "\n")?;
+        println!("wrote {path}");
+    }
+
+    Ok(())
+}
+
+// one untimed warm up, then `runs` timed passes over parse, part 1 and part 2
+fn bench_day(
+    day: &registry::Day,
+    contents: &str,
+    runs: usize,
+) -> Result<Vec<Measurement>, Box<dyn Error>> {
+    (day.time)(contents)?;
+
+    let mut samples: [Vec<Duration>; 3] = Default::default();
+    for _ in 0..runs {
+        let timings = (day.time)(contents)?;
+        for (phase, elapsed) in samples.iter_mut().zip(timings) {
+            phase.push(elapsed);
+        }
+    }
+
+    Ok(["parse", "part1", "part2"]
+        .into_iter()
+        .zip(samples)
+        .map(|(phase, times)| measure(day.number, phase, &times))
+        .collect())
+}
+
+fn measure(day: usize, phase: &'static str, times: &[Duration]) -> Measurement {
+    let millis: Vec<f64> = times.iter().map(|t| t.as_secs_f64() * 1000.0).collect();
+    let (mean_ms, stddev_ms) = mean_stddev(&millis);
+
+    Measurement {
+        day,
+        phase,
+        runs: millis.len(),
+        mean_ms,
+        stddev_ms,
+        min_ms: millis.iter().copied().fold(f64::INFINITY, f64::min),
+    }
+}
+
+// sample standard deviation, so a single run reports 0 rather than NaN
+fn mean_stddev(samples: &[f64]) -> (f64, f64) {
+    if samples.is_empty() {
+        return (0.0, 0.0);
+    }
+
+    let n = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / n;
+    if samples.len() == 1 {
+        return (mean, 0.0);
+    }
+
+    let variance = samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (mean, variance.sqrt())
+}
+
+fn bench_table(results: &[Measurement]) -> String {
+    let mut table = "day | phase |       mean |     stddev |        min\n".to_string();
+    table += "----+-------+------------+------------+-----------\n";
+
+    for m in results {
+        table += &format!(
+            "{:>3} | {:<5} | {:>8.3}ms | {:>8.3}ms | {:>7.3}ms\n",
+            m.day, m.phase, m.mean_ms, m.stddev_ms, m.min_ms
+        );
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(raw: &str) -> Vec<String> {
+        raw.split_whitespace().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn build() {
+        assert_eq!(
+            Config {
+                day: None,
+                runs: DEFAULT_RUNS,
+                json: None,
+            },
+            Config::build(&args("")).unwrap()
+        );
+        assert_eq!(
+            Config {
+                day: Some(6),
+                runs: 3,
+                json: Some("bench.json".to_string()),
+            },
+            Config::build(&args("--day 6 --runs 3 --json bench.json")).unwrap()
+        );
+        assert!(Config::build(&args("--runs 0")).is_err());
+        assert!(Config::build(&args("--runs many")).is_err());
+        assert!(Config::build(&args("--day 26")).is_err());
+        assert!(Config::build(&args("--part 1")).is_err());
+    }
+
+    #[test]
+    fn stats() {
+        let (mean, stddev) = mean_stddev(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+
+        assert_eq!(5.0, mean);
+        assert!((stddev - 2.138).abs() < 0.001);
+        assert_eq!((3.0, 0.0), mean_stddev(&[3.0]));
+        assert_eq!((0.0, 0.0), mean_stddev(&[]));
+    }
+
+    #[test]
+    fn bench_small_input() {
+        let day = registry::find(6).unwrap();
+        let results = bench_day(day, "mjqjpqmgbljsphdztnvjfqwrcgsmlb", 2).unwrap_or_else(|err| {
+            println!("failed to unwrap test result, {err}");
+            Vec::new()
+        });
+
+        assert_eq!(
+            vec!["parse", "part1", "part2"],
+            results.iter().map(|m| m.phase).collect::<Vec<_>>()
+        );
+        assert!(results.iter().all(|m| m.runs == 2 && m.day == 6));
+    }
+
+    #[test]
+    fn table_and_json() {
+        let results = vec![measure(
+            3,
+            "part1",
+            &[Duration::from_millis(1), Duration::from_millis(3)],
+        )];
+
+        assert_eq!(
+            "day | phase |       mean |     stddev |        min
+----+-------+------------+------------+-----------
+  3 | part1 |    2.000ms |    1.414ms |   1.000ms
+",
+            bench_table(&results)
+        );
+
+        let json = serde_json::to_value(&results[0]).unwrap();
+        assert_eq!(3, json["day"]);
+        assert_eq!("part1", json["phase"]);
+        assert_eq!(2.0, json["mean_ms"]);
+    }
+}
@@ -0,0 +1,114 @@
+use std::error::Error;
+use std::path::PathBuf;
+
+use crate::remote::{Client, Remote};
+
+#[derive(Debug, PartialEq)]
+pub struct Config {
+    day: usize,
+    remote: Remote,
+}
+
+impl Config {
+    // flags after `aoc fetch`. --day is required and may name a day that
+    // has no crate yet
+    pub fn build(args: &[String]) -> Result<Config, &'static str> {
+        let mut day = None;
+        let mut remote = Remote::default();
+
+        let mut flags = args.iter();
+        while let Some(flag) = flags.next() {
+            match flag.as_str() {
+                "--day" => {
+                    let value = flags.next().ok_or("--day requires a value")?;
+                    let value = value
+                        .parse::<usize>()
+                        .map_err(|_| "--day must be a number")?;
+                    if !(1..=25).contains(&value) {
+                        return Err("--day must be between 1 and 25");
+                    }
+                    day = Some(value);
+                }
+                _ if remote.flag(flag, &mut flags)? => {}
+                _ => return Err(crate::USAGE),
+            }
+        }
+
+        Ok(Config {
+            day: day.ok_or("fetch needs a --day")?,
+            remote,
+        })
+    }
+}
+
+pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
+    let client = config.remote.client()?;
+    let (path, fetched) = fetch(&client, config.day)?;
+
+    match fetched {
+        true => println!("fetched day {:02} into {}", config.day, path.display()),
+        false => println!(
+            "day {:02} is already cached at {}",
+            config.day,
+            path.display()
+        ),
+    }
+    Ok(())
+}
+
+// inputs never change once published, so a cached day is never downloaded
+// again. the bool says whether the server was asked
+fn fetch(client: &Client, day: usize) -> Result<(PathBuf, bool), Box<dyn Error>> {
+    if client.cache.contains(day) {
+        return Ok((client.cache.path(day), false));
+    }
+
+    let contents = client.input(day)?;
+    Ok((client.cache.store(day, &contents)?, true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::remote::mock;
+    use std::{env, fs};
+
+    fn args(raw: &str) -> Vec<String> {
+        raw.split_whitespace().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn build() {
+        let config = Config::build(&args("--day 16 --year 2021 --session abc")).unwrap();
+
+        assert_eq!(16, config.day);
+        assert!(Config::build(&args("--year 2021")).is_err());
+        assert!(Config::build(&args("--day 26")).is_err());
+        assert!(Config::build(&args("--day 3 --part 1")).is_err());
+    }
+
+    #[test]
+    fn fetches_once() {
+        let dir = env::temp_dir().join(format!("aoc-fetch-test-{}", std::process::id()));
+        let (url, server) = mock::serve(vec![(200, "2-4,6-8\n")]);
+        let raw = format!(
+            "--day 4 --base-url {url} --session abc --year 2022 --cache {}",
+            dir.display()
+        );
+        let config = Config::build(&args(&raw)).unwrap();
+        let client = config.remote.client().unwrap();
+
+        let (path, fetched) = fetch(&client, 4).unwrap_or_else(|err| {
+            println!("failed to unwrap test result, {err}");
+            (PathBuf::new(), false)
+        });
+        assert!(fetched);
+        assert_eq!("2-4,6-8\n", fs::read_to_string(&path).unwrap());
+
+        // the stand-in server only answers once, so this must come from disk
+        assert_eq!((path, false), fetch(&client, 4).unwrap());
+        assert_eq!(1, server.join().unwrap().len());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,276 @@
+use std::error::Error;
+
+use aoc_common::{find_input, read_input, Format, Record};
+
+pub mod bench;
+pub mod fetch;
+pub mod registry;
+pub mod remote;
+pub mod scaffold;
+pub mod submit;
+pub mod verify;
+
+const USAGE: &str =
+    "usage: aoc run (--day N [--part P] [--input PATH] | --all) [--format text|json]
+       aoc bench [--day N] [--runs N] [--json PATH]
+       aoc verify [--day N]
+       aoc fetch --day N [--year Y] [--base-url URL] [--session TOKEN] [--cache DIR]
+       aoc submit --day N --part P [--answer A] [--year Y] [--base-url URL] [--session TOKEN] [--cache DIR]
+       aoc new --day N";
+
+#[derive(Debug, PartialEq)]
+pub enum Config {
+    Run(RunConfig),
+    Bench(bench::Config),
+    Verify(verify::Config),
+    Fetch(fetch::Config),
+    Submit(submit::Config),
+    New(scaffold::Config),
+}
+
+impl Config {
+    pub fn build(args: &[String]) -> Result<Config, &'static str> {
+        match args.get(1).map(|a| a.as_str()) {
+            Some("run") => Ok(Config::Run(RunConfig::build(&args[2..])?)),
+            Some("bench") => Ok(Config::Bench(bench::Config::build(&args[2..])?)),
+            Some("verify") => Ok(Config::Verify(verify::Config::build(&args[2..])?)),
+            Some("fetch") => Ok(Config::Fetch(fetch::Config::build(&args[2..])?)),
+            Some("submit") => Ok(Config::Submit(submit::Config::build(&args[2..])?)),
+            Some("new") => Ok(Config::New(scaffold::Config::build(&args[2..])?)),
+            _ => Err(USAGE),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RunConfig {
+    day: Option<usize>,
+    part: Option<i32>,
+    input: Option<String>,
+    all: bool,
+    format: Format,
+}
+
+impl RunConfig {
+    // flags after `aoc run`
+    pub fn build(args: &[String]) -> Result<RunConfig, &'static str> {
+        let mut config = RunConfig {
+            day: None,
+            part: None,
+            input: None,
+            all: false,
+            format: Format::Text,
+        };
+
+        let mut flags = args.iter();
+        while let Some(flag) = flags.next() {
+            match flag.as_str() {
+                "--all" => config.all = true,
+                "--day" => {
+                    let day = flags.next().ok_or("--day requires a value")?;
+                    let day = day.parse::<usize>().map_err(|_| "--day must be a number")?;
+                    if registry::find(day).is_none() {
+                        return Err("--day does not match a registered day");
+                    }
+                    config.day = Some(day);
+                }
+                "--part" => {
+                    let part = flags.next().ok_or("--part requires a value")?;
+                    let part = part.parse::<i32>().map_err(|_| "--part must be a number")?;
+                    if part != 1 && part != 2 {
+                        return Err("--part must be 1 or 2");
+                    }
+                    config.part = Some(part);
+                }
+                "--input" => {
+                    let input = flags.next().ok_or("--input requires a value")?;
+                    config.input = Some(input.clone());
+                }
+                "--format" => {
+                    let format = flags.next().ok_or("--format requires a value")?;
+                    config.format = format.parse()?;
+                }
+                _ => return Err(USAGE),
+            }
+        }
+
+        if config.all == config.day.is_some() {
+            return Err(USAGE);
+        }
+
+        Ok(config)
+    }
+}
+
+pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
+    match config {
+        Config::Run(config) => run_days(config),
+        Config::Bench(config) => bench::run(config),
+        Config::Verify(config) => verify::run(config),
+        Config::Fetch(config) => fetch::run(config),
+        Config::Submit(config) => submit::run(config),
+        Config::New(config) => scaffold::run(config),
+    }
+}
+
+fn run_days(config: RunConfig) -> Result<(), Box<dyn Error>> {
+    let parts: Vec<i32> = match config.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    let mut rows = Vec::new();
+
+    if config.all {
+        for day in registry::DAYS.iter() {
+            let path = find_input(day.number);
+            let contents = read_input(&path)?;
+            for part in &parts {
+                rows.push(solve_row(day, &path, &contents, *part));
+            }
+        }
+    } else if let Some(number) = config.day {
+        let day = registry::find(number).ok_or("unknown day")?;
+        let path = config.input.unwrap_or_else(|| find_input(number));
+        let contents = read_input(&path)?;
+        for part in &parts {
+            rows.push(solve_row(day, &path, &contents, *part));
+        }
+    }
+
+    match config.format {
+        Format::Text => print!("{}", results_table(&rows)),
+        Format::Json => rows.iter().for_each(|r| println!("{}", r.to_json())),
+    }
+    Ok(())
+}
+
+fn solve_row(day: &registry::Day, path: &str, contents: &str, part: i32) -> Record {
+    Record::timed(day.number, part, path, || (day.solve)(contents, part))
+}
+
+// multi-line answers (day10 draws to a screen) get their first line in the
+// table and the rest printed underneath the row
+fn results_table(rows: &[Record]) -> String {
+    let answers: Vec<String> = rows
+        .iter()
+        .map(|r| match &r.answer {
+            Ok(a) => a.to_string().trim_end().to_string(),
+            Err(e) => format!("error: {e}"),
+        })
+        .collect();
+
+    let width = answers
+        .iter()
+        .map(|a| a.lines().next().unwrap_or("").len())
+        .max()
+        .unwrap_or(0)
+        .max("answer".len());
+
+    let mut table = format!("day | part | {:<width$} | time\n", "answer");
+    table += &format!("----+------+-{}-+---------\n", "-".repeat(width));
+
+    for (row, answer) in rows.iter().zip(answers) {
+        let mut lines = answer.lines();
+        let first = lines.next().unwrap_or("");
+        let millis = row.elapsed.as_secs_f64() * 1000.0;
+        table += &format!(
+            "{:>3} | {:>4} | {:<width$} | {:.3}ms\n",
+            row.day, row.part, first, millis
+        );
+        for line in lines {
+            table += &format!("    |      | {:<width$} |\n", line);
+        }
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::{Answer, AocError};
+    use std::time::Duration;
+
+    fn args(raw: &str) -> Vec<String> {
+        raw.split(' ').map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn build_single_day() {
+        let config = Config::build(&args("aoc run --day 9 --part 2 --input in.txt")).unwrap();
+
+        assert_eq!(
+            Config::Run(RunConfig {
+                day: Some(9),
+                part: Some(2),
+                input: Some("in.txt".to_string()),
+                all: false,
+                format: Format::Text,
+            }),
+            config
+        );
+    }
+
+    #[test]
+    fn build_all() {
+        let config = RunConfig::build(&args("--all")).unwrap();
+
+        assert!(config.all);
+        assert_eq!(None, config.day);
+        assert!(matches!(
+            Config::build(&args("aoc bench --day 3")),
+            Ok(Config::Bench(_))
+        ));
+    }
+
+    #[test]
+    fn build_rejects_bad_values() {
+        assert!(Config::build(&args("aoc run --day nine")).is_err());
+        assert!(Config::build(&args("aoc run --day 26")).is_err());
+        assert!(Config::build(&args("aoc run --day 1 --part 3")).is_err());
+        assert!(Config::build(&args("aoc run --day 1 --all")).is_err());
+        assert!(Config::build(&args("aoc run")).is_err());
+        assert!(Config::build(&args("aoc walk --all")).is_err());
+        assert!(Config::build(&args("aoc run --all --format xml")).is_err());
+    }
+
+    #[test]
+    fn registry_dispatch() {
+        let day = registry::find(9).unwrap();
+        let input = "R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2";
+
+        assert_eq!(Answer::Int(13), (day.solve)(input, 1).unwrap());
+        assert!(registry::DAYS.windows(2).all(|w| w[0].number < w[1].number));
+    }
+
+    #[test]
+    fn table_rows() {
+        let rows = vec![
+            Record {
+                day: 10,
+                part: 2,
+                input: "input.txt".to_string(),
+                answer: Ok(Answer::Image(vec!["##..".to_string(), "..##".to_string()])),
+                elapsed: Duration::from_millis(1),
+            },
+            Record {
+                day: 2,
+                part: 1,
+                input: "input.txt".to_string(),
+                answer: Err(AocError::unsolvable(2, "bad")),
+                elapsed: Duration::from_millis(2),
+            },
+        ];
+
+        assert_eq!(
+            "day | part | answer             | time
+----+------+--------------------+---------
+ 10 |    2 | ##..               | 1.000ms
+    |      | ..##               |
+  2 |    1 | error: day 02: bad | 2.000ms
+",
+            results_table(&rows)
+        );
+    }
+}
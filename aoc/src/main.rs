@@ -0,0 +1,15 @@
+use aoc::Config;
+use std::{env, process};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let config = Config::build(&args).unwrap_or_else(|err| {
+        eprintln!("failed to parse arguments: {err}");
+        process::exit(1);
+    });
+
+    if let Err(e) = aoc::run(config) {
+        eprintln!("application error {e}");
+        process::exit(1);
+    }
+}
@@ -0,0 +1,120 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use aoc_common::{Answer, AocError, Solution};
+
+pub type Solver = fn(&str, i32) -> Result<Answer, AocError>;
+// how long parse, part 1 and part 2 took for one pass over the input
+pub type Timer = fn(&str) -> Result<[Duration; 3], AocError>;
+
+// one entry per day crate, each pointing at that day's Solution so the runner
+// can treat every day the same way
+pub struct Day {
+    pub number: usize,
+    pub solve: Solver,
+    pub time: Timer,
+}
+
+fn solve<S: Solution + Default>(contents: &str, problem_number: i32) -> Result<Answer, AocError> {
+    S::default().solve(contents, problem_number)
+}
+
+fn time<S: Solution + Default>(contents: &str) -> Result<[Duration; 3], AocError> {
+    let solution = S::default();
+
+    let start = Instant::now();
+    let input = black_box(solution.parse(contents)?);
+    let parse = start.elapsed();
+
+    let start = Instant::now();
+    black_box(solution.part1(&input)?);
+    let part1 = start.elapsed();
+
+    let start = Instant::now();
+    black_box(solution.part2(&input)?);
+    let part2 = start.elapsed();
+
+    Ok([parse, part1, part2])
+}
+
+pub static DAYS: [Day; 15] = [
+    Day {
+        number: 1,
+        solve: solve::<day01::Day01>,
+        time: time::<day01::Day01>,
+    },
+    Day {
+        number: 2,
+        solve: solve::<day02::Day02>,
+        time: time::<day02::Day02>,
+    },
+    Day {
+        number: 3,
+        solve: solve::<day03::Day03>,
+        time: time::<day03::Day03>,
+    },
+    Day {
+        number: 4,
+        solve: solve::<day04::Day04>,
+        time: time::<day04::Day04>,
+    },
+    Day {
+        number: 5,
+        solve: solve::<day05::Day05>,
+        time: time::<day05::Day05>,
+    },
+    Day {
+        number: 6,
+        solve: solve::<day06::Day06>,
+        time: time::<day06::Day06>,
+    },
+    Day {
+        number: 7,
+        solve: solve::<day07::Day07>,
+        time: time::<day07::Day07>,
+    },
+    Day {
+        number: 8,
+        solve: solve::<day08::Day08>,
+        time: time::<day08::Day08>,
+    },
+    Day {
+        number: 9,
+        solve: solve::<day09::Day09>,
+        time: time::<day09::Day09>,
+    },
+    Day {
+        number: 10,
+        solve: solve::<day10::Day10>,
+        time: time::<day10::Day10>,
+    },
+    Day {
+        number: 11,
+        solve: solve::<day11::Day11>,
+        time: time::<day11::Day11>,
+    },
+    Day {
+        number: 12,
+        solve: solve::<day12::Day12>,
+        time: time::<day12::Day12>,
+    },
+    Day {
+        number: 13,
+        solve: solve::<day13::Day13>,
+        time: time::<day13::Day13>,
+    },
+    Day {
+        number: 14,
+        solve: solve::<day14::Day14>,
+        time: time::<day14::Day14>,
+    },
+    Day {
+        number: 15,
+        solve: solve::<day15::Day15>,
+        time: time::<day15::Day15>,
+    },
+];
+
+pub fn find(number: usize) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == number)
+}
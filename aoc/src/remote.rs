@@ -0,0 +1,275 @@
+use std::env;
+use std::error::Error;
+use std::time::Duration;
+
+use aoc_common::{default_cache_dir, InputCache, DEFAULT_YEAR};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = concat!("aoc-runner/", env!("CARGO_PKG_VERSION"));
+
+// where puzzles are fetched from and who is asking. every setting can be
+// given as a flag and otherwise falls back to its AOC_* environment variable
+#[derive(Debug, Default, PartialEq)]
+pub struct Remote {
+    base_url: Option<String>,
+    session: Option<String>,
+    year: Option<u32>,
+    cache_dir: Option<String>,
+}
+
+impl Remote {
+    // Ok(false) when flag is not one of the shared remote flags
+    pub fn flag<'a>(
+        &mut self,
+        flag: &str,
+        flags: &mut impl Iterator<Item = &'a String>,
+    ) -> Result<bool, &'static str> {
+        match flag {
+            "--base-url" => {
+                let url = flags.next().ok_or("--base-url requires a value")?;
+                self.base_url = Some(url.clone());
+            }
+            "--session" => {
+                let session = flags.next().ok_or("--session requires a value")?;
+                self.session = Some(session.clone());
+            }
+            "--year" => {
+                let year = flags.next().ok_or("--year requires a value")?;
+                let year = year.parse::<u32>().map_err(|_| "--year must be a number")?;
+                self.year = Some(year);
+            }
+            "--cache" => {
+                let dir = flags.next().ok_or("--cache requires a value")?;
+                self.cache_dir = Some(dir.clone());
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    // whether --session or --cache picked the account to work with
+    pub fn picks_account(&self) -> bool {
+        self.session.is_some() || self.cache_dir.is_some()
+    }
+
+    pub fn client(&self) -> Result<Client, Box<dyn Error>> {
+        let session = match &self.session {
+            Some(session) => session.clone(),
+            None => env::var("AOC_SESSION")
+                .map_err(|_| "no session token, pass --session or set AOC_SESSION")?,
+        };
+        let year = match (self.year, env::var("AOC_YEAR")) {
+            (Some(year), _) => year,
+            (None, Ok(year)) => year.parse().map_err(|_| "AOC_YEAR must be a number")?,
+            (None, Err(_)) => DEFAULT_YEAR,
+        };
+        let base_url = match &self.base_url {
+            Some(url) => url.clone(),
+            None => env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string()),
+        };
+        let cache_dir = self.cache_dir.clone().unwrap_or_else(default_cache_dir);
+
+        Ok(Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            cache: InputCache::new(cache_dir, year, &session),
+            session,
+            year,
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .user_agent(USER_AGENT)
+                .build(),
+        })
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    year: u32,
+    pub cache: InputCache,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn year(&self) -> u32 {
+        self.year
+    }
+
+    // the puzzle input for one day, straight from the server
+    pub fn input(&self, day: usize) -> Result<String, Box<dyn Error>> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, self.year, day);
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+
+        read_response(&url, response)
+    }
+
+    // posts an answer and hands back the page the server replies with
+    pub fn submit(&self, day: usize, part: i32, answer: &str) -> Result<String, Box<dyn Error>> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, self.year, day);
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+
+        read_response(&url, response)
+    }
+}
+
+// error statuses become errors carrying the start of what the server said
+fn read_response(
+    url: &str,
+    response: Result<ureq::Response, ureq::Error>,
+) -> Result<String, Box<dyn Error>> {
+    match response {
+        Ok(response) => Ok(response.into_string()?),
+        Err(ureq::Error::Status(code, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            let first = body.lines().next().unwrap_or("").trim();
+            Err(format!("{url} returned {code}: {first}").into())
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+// a stand-in for the puzzle server. each connection gets the next canned
+// response and the requests are handed back once they have all been served
+#[cfg(test)]
+pub(crate) mod mock {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    pub struct Request {
+        pub head: String,
+        pub body: String,
+    }
+
+    pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<Request>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+
+                let mut head = String::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                    head += &line;
+                }
+
+                let length = head
+                    .lines()
+                    .find_map(|l| {
+                        l.to_lowercase()
+                            .strip_prefix("content-length:")
+                            .map(|n| n.trim().to_string())
+                    })
+                    .map_or(0, |n| n.parse().unwrap());
+                let mut request_body = vec![0; length];
+                reader.read_exact(&mut request_body).unwrap();
+
+                let mut stream = reader.into_inner();
+                let reason = if status == 200 { "OK" } else { "Error" };
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} {reason}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+
+                requests.push(Request {
+                    head,
+                    body: String::from_utf8(request_body).unwrap(),
+                });
+            }
+            requests
+        });
+
+        (url, handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remote(url: &str) -> Remote {
+        Remote {
+            base_url: Some(format!("{url}/")),
+            session: Some("abc".to_string()),
+            year: Some(2022),
+            cache_dir: Some("unused".to_string()),
+        }
+    }
+
+    #[test]
+    fn flags() {
+        let mut remote = Remote::default();
+        let value = |v: &str| vec![v.to_string()];
+
+        assert_eq!(Ok(true), remote.flag("--year", &mut value("2021").iter()));
+        assert_eq!(Ok(true), remote.flag("--session", &mut value("abc").iter()));
+        assert_eq!(Ok(false), remote.flag("--day", &mut value("1").iter()));
+        assert_eq!(Some(2021), remote.year);
+        assert_eq!(Some("abc".to_string()), remote.session);
+        assert!(remote.flag("--year", &mut value("soon").iter()).is_err());
+        assert!(remote.flag("--cache", &mut Vec::new().iter()).is_err());
+    }
+
+    #[test]
+    fn input_request() {
+        let (url, server) = mock::serve(vec![(200, "1000\n2000\n")]);
+        let client = remote(&url).client().unwrap();
+
+        assert_eq!("1000\n2000\n", client.input(1).unwrap());
+
+        let requests = server.join().unwrap();
+        assert!(requests[0]
+            .head
+            .starts_with("GET /2022/day/1/input HTTP/1.1\r\n"));
+        assert!(requests[0].head.contains("session=abc"));
+        assert!(requests[0].body.is_empty());
+    }
+
+    #[test]
+    fn answer_request() {
+        let (url, server) = mock::serve(vec![(200, "<article>That's the right answer!</article>")]);
+        let client = remote(&url).client().unwrap();
+
+        assert!(client
+            .submit(6, 2, "2665")
+            .unwrap()
+            .contains("right answer"));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0]
+            .head
+            .starts_with("POST /2022/day/6/answer HTTP/1.1\r\n"));
+        assert_eq!("level=2&answer=2665", requests[0].body);
+    }
+
+    #[test]
+    fn error_status() {
+        let (url, server) = mock::serve(vec![(400, "Puzzle inputs differ by user.\n")]);
+        let client = remote(&url).client().unwrap();
+
+        assert_eq!(
+            format!("{url}/2022/day/4/input returned 400: Puzzle inputs differ by user."),
+            client.input(4).unwrap_err().to_string()
+        );
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,414 @@
+use std::error::Error;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, PartialEq)]
+pub struct Config {
+    day: usize,
+    root: PathBuf,
+}
+
+impl Config {
+    // flags after `aoc new`. --root is the workspace to add the day to and is
+    // only really useful for trying the generator out somewhere else
+    pub fn build(args: &[String]) -> Result<Config, &'static str> {
+        let mut day = None;
+        let mut root = PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/.."));
+
+        let mut flags = args.iter();
+        while let Some(flag) = flags.next() {
+            match flag.as_str() {
+                "--day" => {
+                    let value = flags.next().ok_or("--day requires a value")?;
+                    let value = value
+                        .parse::<usize>()
+                        .map_err(|_| "--day must be a number")?;
+                    if !(1..=25).contains(&value) {
+                        return Err("--day must be between 1 and 25");
+                    }
+                    day = Some(value);
+                }
+                "--root" => {
+                    let value = flags.next().ok_or("--root requires a value")?;
+                    root = PathBuf::from(value);
+                }
+                _ => return Err(crate::USAGE),
+            }
+        }
+
+        Ok(Config {
+            day: day.ok_or("new needs a --day")?,
+            root,
+        })
+    }
+}
+
+pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
+    let name = scaffold(&config.root, config.day)?;
+    println!("created {name}, registered it with the workspace and aoc");
+    println!("fetch its input with: aoc fetch --day {}", config.day);
+    Ok(())
+}
+
+// every file is checked before anything is written, so a day that already
+// exists (or a tree that has been rearranged) is left exactly as it was
+fn scaffold(root: &Path, day: usize) -> Result<String, Box<dyn Error>> {
+    let name = format!("day{day:02}");
+    let crate_dir = root.join(&name);
+    if crate_dir.exists() {
+        return Err(format!("{} already exists", crate_dir.display()).into());
+    }
+
+    let workspace_path = root.join("Cargo.toml");
+    let runner_path = root.join("aoc/Cargo.toml");
+    let registry_path = root.join("aoc/src/registry.rs");
+
+    let workspace = add_member(&read(&workspace_path)?, &name)?;
+    let runner = add_dependency(&read(&runner_path)?, &name)?;
+    let registry = register(&read(&registry_path)?, day)?;
+
+    fs::create_dir_all(crate_dir.join("src"))?;
+    fs::write(crate_dir.join("Cargo.toml"), manifest(&name))?;
+    fs::write(crate_dir.join("src/main.rs"), main_rs(&name))?;
+    fs::write(crate_dir.join("src/lib.rs"), lib_rs(day))?;
+    fs::write(crate_dir.join("answers.toml"), ANSWERS)?;
+
+    fs::write(workspace_path, workspace)?;
+    fs::write(runner_path, runner)?;
+    fs::write(registry_path, registry)?;
+
+    Ok(name)
+}
+
+fn read(path: &Path) -> Result<String, Box<dyn Error>> {
+    fs::read_to_string(path)
+        .map_err(|err| format!("could not read {}: {err}", path.display()).into())
+}
+
+// members are kept in order, so the new day goes before the first day that
+// sorts after it
+fn add_member(workspace: &str, name: &str) -> Result<String, &'static str> {
+    let lines: Vec<&str> = workspace.lines().collect();
+    let start = lines
+        .iter()
+        .position(|l| *l == "members = [")
+        .ok_or("workspace Cargo.toml has no members list")?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|l| *l == "]")
+            .ok_or("members list is not closed")?;
+
+    Ok(insert_day(
+        lines,
+        start + 1..end,
+        &format!("    \"{name}\","),
+    ))
+}
+
+fn add_dependency(manifest: &str, name: &str) -> Result<String, &'static str> {
+    let lines: Vec<&str> = manifest.lines().collect();
+    let start = lines
+        .iter()
+        .position(|l| *l == "[dependencies]")
+        .ok_or("aoc Cargo.toml has no [dependencies]")?;
+    let end = lines[start + 1..]
+        .iter()
+        .position(|l| l.starts_with('['))
+        .map_or(lines.len(), |i| start + 1 + i);
+
+    Ok(insert_day(
+        lines,
+        start + 1..end,
+        &format!("{name} = {{ path = \"../{name}\" }}"),
+    ))
+}
+
+// puts entry among the day lines in range, keeping them sorted, or at the
+// end of the range when it has no days yet
+fn insert_day<'a>(mut lines: Vec<&'a str>, range: Range<usize>, entry: &'a str) -> String {
+    let is_day = |l: &str| l.trim_start().trim_start_matches('"').starts_with("day");
+    let days: Vec<usize> = range.clone().filter(|i| is_day(lines[*i])).collect();
+
+    let at = days
+        .iter()
+        .find(|i| lines[**i] > entry)
+        .copied()
+        .or_else(|| days.last().map(|i| i + 1))
+        .unwrap_or(range.end);
+
+    lines.insert(at, entry);
+    lines.join("\n") + "\n"
+}
+
+// adds a Day entry to DAYS, in order, and bumps the array length to match
+fn register(registry: &str, day: usize) -> Result<String, &'static str> {
+    let start = registry
+        .find("pub static DAYS: [Day; ")
+        .ok_or("registry has no DAYS table")?;
+    let len_start = start + "pub static DAYS: [Day; ".len();
+    let len_end = len_start
+        + registry[len_start..]
+            .find(']')
+            .ok_or("DAYS has no length")?;
+    let len: usize = registry[len_start..len_end]
+        .parse()
+        .map_err(|_| "DAYS length is not a number")?;
+    let end = start
+        + registry[start..]
+            .find("\n];")
+            .ok_or("DAYS table is not closed")?
+        + 1;
+
+    let numbers: Vec<(usize, usize)> = registry[start..end]
+        .match_indices("    Day {\n        number: ")
+        .map(|(i, m)| {
+            let digits = &registry[start + i + m.len()..];
+            let n = digits[..digits.find(',').unwrap_or(0)].parse().unwrap_or(0);
+            (start + i, n)
+        })
+        .collect();
+    if numbers.iter().any(|(_, n)| *n == day) {
+        return Err("that day is already registered");
+    }
+
+    let at = numbers
+        .iter()
+        .find(|(_, n)| *n > day)
+        .map_or(end, |(i, _)| *i);
+    let entry = format!(
+        "    Day {{\n        number: {day},\n        solve: solve::<day{day:02}::Day{day:02}>,\n        time: time::<day{day:02}::Day{day:02}>,\n    }},\n"
+    );
+
+    Ok(format!(
+        "{}{}{}{entry}{}",
+        &registry[..len_start],
+        len + 1,
+        &registry[len_end..at],
+        &registry[at..]
+    ))
+}
+
+fn manifest(name: &str) -> String {
+    format!(
+        r#"[package]
+name = "{name}"
+version = "0.1.0"
+edition = "2021"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+aoc-common = {{ path = "../aoc-common" }}
+"#
+    )
+}
+
+fn main_rs(name: &str) -> String {
+    format!(
+        r#"use {name}::Config;
+use std::{{env, process}};
+
+fn main() {{
+    let args: Vec<String> = env::args().collect();
+    let config = Config::build(&args).unwrap_or_else(|err| {{
+        eprintln!("failed to parse arguments: {{err}}");
+        process::exit(1);
+    }});
+
+    if let Err(e) = {name}::run(config) {{
+        eprintln!("application error {{e}}");
+        process::exit(1);
+    }}
+}}
+"#
+    )
+}
+
+fn lib_rs(day: usize) -> String {
+    format!(
+        r#"use std::error::Error;
+
+pub use aoc_common::Config;
+use aoc_common::{{Answer, AocError, Solution}};
+
+const DAY: usize = {day};
+
+pub fn run(config: Config) -> Result<(), Box<dyn Error>> {{
+    aoc_common::run(DAY, &Day{day:02}, config)
+}}
+
+#[derive(Default)]
+pub struct Day{day:02};
+
+impl Solution for Day{day:02} {{
+    type Input = String;
+
+    fn parse(&self, contents: &str) -> Result<Self::Input, AocError> {{
+        Ok(contents.to_string())
+    }}
+
+    fn part1(&self, _input: &Self::Input) -> Result<Answer, AocError> {{
+        Err(AocError::unsolvable(DAY, "part 1 is not solved yet"))
+    }}
+
+    fn part2(&self, _input: &Self::Input) -> Result<Answer, AocError> {{
+        Err(AocError::unsolvable(DAY, "part 2 is not solved yet"))
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    // the example from the puzzle description
+    const EXAMPLE: &str = "";
+
+    #[test]
+    #[ignore = "needs the example input and its answer"]
+    fn p1() {{
+        let result = Day{day:02}.solve(EXAMPLE, 1).unwrap_or_else(|err| {{
+            println!("failed to unwrap test result, {{err}}");
+            Answer::Int(0)
+        }});
+
+        assert_eq!(Answer::Int(0), result);
+    }}
+
+    #[test]
+    #[ignore = "needs the example input and its answer"]
+    fn p2() {{
+        let result = Day{day:02}.solve(EXAMPLE, 2).unwrap_or_else(|err| {{
+            println!("failed to unwrap test result, {{err}}");
+            Answer::Int(0)
+        }});
+
+        assert_eq!(Answer::Int(0), result);
+    }}
+}}
+"#
+    )
+}
+
+const ANSWERS: &str = "# the answers for input.txt, for aoc verify. add each part once it is solved
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    const WORKSPACE: &str = "[workspace]
+resolver = \"2\"
+members = [
+    \"aoc\",
+    \"aoc-common\",
+    \"day01\",
+    \"day03\",
+]
+";
+
+    const RUNNER: &str = "[package]
+name = \"aoc\"
+
+[dependencies]
+aoc-common = { path = \"../aoc-common\" }
+day01 = { path = \"../day01\" }
+day03 = { path = \"../day03\" }
+serde = \"1.0.150\"
+";
+
+    const REGISTRY: &str = "pub static DAYS: [Day; 2] = [
+    Day {
+        number: 1,
+        solve: solve::<day01::Day01>,
+        time: time::<day01::Day01>,
+    },
+    Day {
+        number: 3,
+        solve: solve::<day03::Day03>,
+        time: time::<day03::Day03>,
+    },
+];
+";
+
+    #[test]
+    fn build() {
+        let config = Config::build(&["--day".to_string(), "16".to_string()]).unwrap();
+
+        assert_eq!(16, config.day);
+        assert!(config.root.join("aoc/src/registry.rs").exists());
+        assert!(Config::build(&[]).is_err());
+        assert!(Config::build(&["--day".to_string(), "0".to_string()]).is_err());
+    }
+
+    #[test]
+    fn workspace_members() {
+        assert!(add_member(WORKSPACE, "day02")
+            .unwrap()
+            .contains("    \"day01\",\n    \"day02\",\n    \"day03\",\n]"));
+        assert!(add_member(WORKSPACE, "day16")
+            .unwrap()
+            .ends_with("    \"day03\",\n    \"day16\",\n]\n"));
+    }
+
+    #[test]
+    fn runner_dependencies() {
+        assert!(add_dependency(RUNNER, "day02")
+            .unwrap()
+            .contains("day01 = { path = \"../day01\" }\nday02 = { path = \"../day02\" }\nday03"));
+        assert!(add_dependency(RUNNER, "day16")
+            .unwrap()
+            .contains("day03 = { path = \"../day03\" }\nday16 = { path = \"../day16\" }\nserde"));
+    }
+
+    #[test]
+    fn registry_entries() {
+        let registry = register(REGISTRY, 2).unwrap();
+
+        assert!(registry.starts_with("pub static DAYS: [Day; 3] = [\n"));
+        assert!(registry.contains(
+            "        number: 2,\n        solve: solve::<day02::Day02>,\n        time: time::<day02::Day02>,\n    },\n    Day {\n        number: 3,"
+        ));
+        assert!(register(REGISTRY, 16)
+            .unwrap()
+            .ends_with("time: time::<day16::Day16>,\n    },\n];\n"));
+        assert!(register(REGISTRY, 3).is_err());
+    }
+
+    #[test]
+    fn scaffolds_once() {
+        let root = env::temp_dir().join(format!("aoc-new-test-{}", std::process::id()));
+        fs::create_dir_all(root.join("aoc/src")).unwrap();
+        fs::write(root.join("Cargo.toml"), WORKSPACE).unwrap();
+        fs::write(root.join("aoc/Cargo.toml"), RUNNER).unwrap();
+        fs::write(root.join("aoc/src/registry.rs"), REGISTRY).unwrap();
+
+        let name = scaffold(&root, 16).unwrap_or_else(|err| {
+            println!("failed to unwrap test result, {err}");
+            String::new()
+        });
+        assert_eq!("day16", name);
+
+        let lib = fs::read_to_string(root.join("day16/src/lib.rs")).unwrap();
+        assert!(lib.contains("pub struct Day16;"));
+        assert!(lib.contains("const DAY: usize = 16;"));
+        assert!(fs::read_to_string(root.join("day16/src/main.rs"))
+            .unwrap()
+            .contains("day16::run(config)"));
+        assert!(fs::read_to_string(root.join("aoc/src/registry.rs"))
+            .unwrap()
+            .contains("[Day; 3]"));
+
+        // a second run must not touch anything
+        fs::write(root.join("day16/src/lib.rs"), "// solved").unwrap();
+        assert!(scaffold(&root, 16).is_err());
+        assert_eq!(
+            "// solved",
+            fs::read_to_string(root.join("day16/src/lib.rs")).unwrap()
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
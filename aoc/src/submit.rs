@@ -0,0 +1,521 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use aoc_common::{local_or_cached, read_input, Answer};
+use serde::{Deserialize, Serialize};
+
+use crate::registry;
+use crate::remote::{Client, Remote};
+
+#[derive(Debug, PartialEq)]
+pub struct Config {
+    day: usize,
+    part: i32,
+    answer: Option<String>,
+    remote: Remote,
+}
+
+impl Config {
+    // flags after `aoc submit`. without --answer the day's solution is run
+    // on its input and that answer is sent
+    pub fn build(args: &[String]) -> Result<Config, &'static str> {
+        let mut day = None;
+        let mut part = None;
+        let mut answer = None;
+        let mut remote = Remote::default();
+
+        let mut flags = args.iter();
+        while let Some(flag) = flags.next() {
+            match flag.as_str() {
+                "--day" => {
+                    let value = flags.next().ok_or("--day requires a value")?;
+                    let value = value
+                        .parse::<usize>()
+                        .map_err(|_| "--day must be a number")?;
+                    if !(1..=25).contains(&value) {
+                        return Err("--day must be between 1 and 25");
+                    }
+                    day = Some(value);
+                }
+                "--part" => {
+                    let value = flags.next().ok_or("--part requires a value")?;
+                    let value = value
+                        .parse::<i32>()
+                        .map_err(|_| "--part must be a number")?;
+                    if value != 1 && value != 2 {
+                        return Err("--part must be 1 or 2");
+                    }
+                    part = Some(value);
+                }
+                "--answer" => {
+                    let value = flags.next().ok_or("--answer requires a value")?;
+                    answer = Some(value.trim().to_string());
+                }
+                _ if remote.flag(flag, &mut flags)? => {}
+                _ => return Err(crate::USAGE),
+            }
+        }
+
+        Ok(Config {
+            day: day.ok_or("submit needs a --day")?,
+            part: part.ok_or("submit needs a --part")?,
+            answer,
+            remote,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Incorrect => write!(f, "wrong"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+        }
+    }
+}
+
+// what the server made of a submission. wrong answers come with a cooldown
+// before the next guess is accepted, in seconds
+#[derive(Debug, PartialEq)]
+enum Response {
+    Verdict(Verdict, Option<u64>),
+    Wait(u64),
+    AlreadySolved,
+    Unknown(String),
+}
+
+// the answer pages are html with the message inside the only <article>
+fn parse_response(page: &str) -> Response {
+    let article = match (page.find("<article>"), page.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &page[start..end],
+        _ => page,
+    };
+    let text = strip_tags(article);
+
+    if text.contains("That's the right answer") {
+        Response::Verdict(Verdict::Correct, None)
+    } else if text.contains("That's not the right answer") {
+        let verdict = if text.contains("too high") {
+            Verdict::TooHigh
+        } else if text.contains("too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Incorrect
+        };
+        Response::Verdict(verdict, wrong_answer_wait(&text))
+    } else if text.contains("You gave an answer too recently") {
+        Response::Wait(time_left(&text).unwrap_or(60))
+    } else if text.contains("Did you already complete it") {
+        Response::AlreadySolved
+    } else {
+        Response::Unknown(text.trim().chars().take(200).collect())
+    }
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+// "please wait one minute before trying again" or "please wait 5 minutes ..."
+fn wrong_answer_wait(text: &str) -> Option<u64> {
+    let text = text.to_lowercase();
+    let rest = &text[text.find("please wait ")? + "please wait ".len()..];
+    let mut words = rest.split_whitespace();
+    let count = match words.next()? {
+        "one" => 1,
+        n => n.parse::<u64>().ok()?,
+    };
+    match words.next()? {
+        unit if unit.starts_with("minute") => Some(count * 60),
+        unit if unit.starts_with("second") => Some(count),
+        _ => None,
+    }
+}
+
+// "You have 1m 23s left to wait" or "You have 34s left to wait"
+fn time_left(text: &str) -> Option<u64> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = text[start..].find(" left to wait")? + start;
+
+    text[start..end]
+        .split_whitespace()
+        .try_fold(0, |total, part| {
+            let (n, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let n = n.parse::<u64>().ok()?;
+            match unit {
+                "h" => Some(total + n * 3600),
+                "m" => Some(total + n * 60),
+                "s" => Some(total + n),
+                _ => None,
+            }
+        })
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Guess {
+    day: usize,
+    part: i32,
+    answer: String,
+    verdict: Verdict,
+    // seconds since the unix epoch
+    at: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Cooldown {
+    day: usize,
+    part: i32,
+    until: u64,
+}
+
+// every answer sent for one account and year, kept next to the cached
+// inputs so guesses that are known to be wrong are never sent twice
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct Ledger {
+    guesses: Vec<Guess>,
+    cooldowns: Vec<Cooldown>,
+}
+
+impl Ledger {
+    fn load(path: &Path) -> Result<Ledger, Box<dyn Error>> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Ok(serde_json::from_str(&contents)
+                .map_err(|err| format!("{}: {err}", path.display()))?),
+            Err(_) => Ok(Ledger::default()),
+        }
+    }
+
+    fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+
+    fn guesses(&self, day: usize, part: i32) -> impl Iterator<Item = &Guess> + '_ {
+        self.guesses
+            .iter()
+            .filter(move |g| g.day == day && g.part == part)
+    }
+
+    // why this answer should not be sent, if there is a reason
+    fn refuse(&self, day: usize, part: i32, answer: &str, now: u64) -> Option<String> {
+        if let Some(correct) = self
+            .guesses(day, part)
+            .find(|g| g.verdict == Verdict::Correct)
+        {
+            return Some(format!(
+                "day {day:02} part {part} is already solved, the answer was {}",
+                correct.answer
+            ));
+        }
+
+        if let Some(guess) = self.guesses(day, part).find(|g| g.answer == answer) {
+            return Some(format!(
+                "{answer} was already guessed and was {}",
+                guess.verdict
+            ));
+        }
+
+        let until = self
+            .cooldowns
+            .iter()
+            .filter(|c| c.day == day && c.part == part)
+            .map(|c| c.until)
+            .max();
+        if let Some(until) = until.filter(|until| *until > now) {
+            return Some(format!("the server asked to wait another {}s", until - now));
+        }
+
+        // numeric guesses can also be ruled out by the bounds found so far
+        let n = answer.parse::<i128>().ok()?;
+        let bound = |verdict| {
+            self.guesses(day, part)
+                .filter(move |g| g.verdict == verdict)
+                .filter_map(|g| g.answer.parse::<i128>().ok())
+        };
+        if let Some(high) = bound(Verdict::TooHigh).filter(|h| n >= *h).min() {
+            return Some(format!("{answer} is too high, {high} already was"));
+        }
+        if let Some(low) = bound(Verdict::TooLow).filter(|l| n <= *l).max() {
+            return Some(format!("{answer} is too low, {low} already was"));
+        }
+
+        None
+    }
+
+    fn record(&mut self, day: usize, part: i32, answer: &str, response: &Response, now: u64) {
+        let wait = match response {
+            Response::Verdict(verdict, wait) => {
+                self.guesses.push(Guess {
+                    day,
+                    part,
+                    answer: answer.to_string(),
+                    verdict: *verdict,
+                    at: now,
+                });
+                *wait
+            }
+            Response::Wait(wait) => Some(*wait),
+            Response::AlreadySolved | Response::Unknown(_) => None,
+        };
+
+        if let Some(wait) = wait {
+            self.cooldowns.retain(|c| c.day != day || c.part != part);
+            self.cooldowns.push(Cooldown {
+                day,
+                part,
+                until: now + wait,
+            });
+        }
+    }
+}
+
+pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
+    let client = config.remote.client()?;
+    let answer = match config.answer {
+        Some(answer) => answer,
+        None => {
+            let path = input_path(&client, config.day, config.remote.picks_account())?;
+            solve(&path, config.day, config.part)?
+        }
+    };
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let response = submit(&client, config.day, config.part, &answer, now)?;
+
+    match response {
+        Response::Verdict(Verdict::Correct, _) => println!("{answer} is correct"),
+        Response::Verdict(verdict, wait) => {
+            println!("{answer} is {verdict}");
+            if let Some(wait) = wait {
+                println!("the next guess can be sent in {wait}s");
+            }
+        }
+        Response::Wait(wait) => println!("answered too recently, wait {wait}s"),
+        Response::AlreadySolved => println!("that part has already been solved"),
+        Response::Unknown(text) => println!("unexpected response: {text}"),
+    }
+    Ok(())
+}
+
+// when --session or --cache picked the account, the answer has to come from
+// that account's cached input. otherwise the day's input.txt comes first,
+// as it does for the day binaries
+fn input_path(client: &Client, day: usize, picked: bool) -> Result<String, Box<dyn Error>> {
+    let cached = client.cache.contains(day).then(|| client.cache.path(day));
+    match (cached, picked) {
+        (Some(path), true) => Ok(path.display().to_string()),
+        (None, true) => Err(format!(
+            "day {day:02} is not cached for that account, run aoc fetch --day {day} first"
+        )
+        .into()),
+        (cached, false) => Ok(local_or_cached(day, cached)),
+    }
+}
+
+fn solve(path: &str, day: usize, part: i32) -> Result<String, Box<dyn Error>> {
+    let entry = registry::find(day).ok_or("that day has no solution yet, pass --answer")?;
+    let contents = read_input(path)?;
+
+    match (entry.solve)(&contents, part)? {
+        Answer::Image(_) => {
+            Err("the answer is drawn as an image, read it and pass --answer".into())
+        }
+        answer => Ok(answer.to_string()),
+    }
+}
+
+// checks the ledger before anything is sent and records the outcome after
+fn submit(
+    client: &Client,
+    day: usize,
+    part: i32,
+    answer: &str,
+    now: u64,
+) -> Result<Response, Box<dyn Error>> {
+    let path = client.cache.user_dir().join("guesses.json");
+    let mut ledger = Ledger::load(&path)?;
+
+    if let Some(reason) = ledger.refuse(day, part, answer, now) {
+        return Err(format!("not submitting, {reason}").into());
+    }
+
+    let response = parse_response(&client.submit(day, part, answer)?);
+    ledger.record(day, part, answer, &response, now);
+    ledger.save(&path)?;
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::remote::mock;
+    use std::env;
+
+    const TOO_HIGH: &str = "<main><article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href=\"/2022/about\">about page</a>.  Please wait one minute before trying again. <a href=\"/2022/day/1\">[Return to Day 1]</a></p></article></main>";
+    const CORRECT: &str = "<main><article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to collecting enough star fruit.</p></article></main>";
+    const WAIT: &str = "<main><article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait. <a href=\"/2022/day/1\">[Return to Day 1]</a></p></article></main>";
+    const SOLVED: &str = "<main><article><p>You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2022/day/1\">[Return to Day 1]</a></p></article></main>";
+
+    fn args(raw: &str) -> Vec<String> {
+        raw.split_whitespace().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn build() {
+        let config = Config::build(&args("--day 10 --part 2 --answer EHZFZHCZ")).unwrap();
+
+        assert_eq!(Some("EHZFZHCZ".to_string()), config.answer);
+        assert!(Config::build(&args("--day 10")).is_err());
+        assert!(Config::build(&args("--part 1")).is_err());
+        assert!(Config::build(&args("--day 1 --part 3")).is_err());
+    }
+
+    #[test]
+    fn responses() {
+        assert_eq!(
+            Response::Verdict(Verdict::TooHigh, Some(60)),
+            parse_response(TOO_HIGH)
+        );
+        assert_eq!(
+            Response::Verdict(Verdict::Correct, None),
+            parse_response(CORRECT)
+        );
+        assert_eq!(Response::Wait(83), parse_response(WAIT));
+        assert_eq!(Response::AlreadySolved, parse_response(SOLVED));
+        assert_eq!(
+            Some(300),
+            wrong_answer_wait("please wait 5 minutes before trying again")
+        );
+        assert!(matches!(
+            parse_response("<html>Log in</html>"),
+            Response::Unknown(_)
+        ));
+    }
+
+    #[test]
+    fn ledger_refusals() {
+        let mut ledger = Ledger::default();
+        ledger.record(
+            1,
+            1,
+            "500",
+            &Response::Verdict(Verdict::TooHigh, Some(60)),
+            1000,
+        );
+        ledger.record(1, 1, "100", &Response::Verdict(Verdict::TooLow, None), 1100);
+
+        assert!(ledger
+            .refuse(1, 1, "500", 2000)
+            .unwrap()
+            .contains("already guessed"));
+        assert!(ledger
+            .refuse(1, 1, "300", 1030)
+            .unwrap()
+            .contains("wait another 30s"));
+        assert!(ledger
+            .refuse(1, 1, "600", 2000)
+            .unwrap()
+            .contains("too high"));
+        assert!(ledger.refuse(1, 1, "50", 2000).unwrap().contains("too low"));
+        assert_eq!(None, ledger.refuse(1, 1, "300", 2000));
+        assert_eq!(None, ledger.refuse(1, 2, "500", 2000));
+
+        ledger.record(
+            1,
+            1,
+            "300",
+            &Response::Verdict(Verdict::Correct, None),
+            2000,
+        );
+        assert!(ledger
+            .refuse(1, 1, "301", 3000)
+            .unwrap()
+            .contains("already solved"));
+    }
+
+    #[test]
+    fn picked_accounts_use_their_cached_input() {
+        let dir = env::temp_dir().join(format!("aoc-submit-input-{}", std::process::id()));
+        let raw = format!("--day 1 --part 1 --session abc --cache {}", dir.display());
+        let config = Config::build(&args(&raw)).unwrap();
+        let client = config.remote.client().unwrap();
+
+        // day01/input.txt is committed, but the account has nothing cached
+        assert!(input_path(&client, 1, true).is_err());
+        assert_eq!(
+            local_or_cached(1, None),
+            input_path(&client, 1, false).unwrap()
+        );
+
+        client.cache.store(1, "100\n\n200\n").unwrap();
+        let cached = client.cache.path(1).display().to_string();
+        assert_eq!(cached, input_path(&client, 1, true).unwrap());
+        assert_eq!("200", solve(&cached, 1, 1).unwrap());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn submits_through_the_ledger() {
+        let dir = env::temp_dir().join(format!("aoc-submit-test-{}", std::process::id()));
+        let (url, server) = mock::serve(vec![(200, TOO_HIGH), (200, CORRECT)]);
+        let raw = format!(
+            "--day 1 --part 1 --base-url {url} --session abc --cache {}",
+            dir.display()
+        );
+        let config = Config::build(&args(&raw)).unwrap();
+        let client = config.remote.client().unwrap();
+
+        let response = submit(&client, 1, 1, "500", 1000).unwrap_or_else(|err| {
+            println!("failed to unwrap test result, {err}");
+            Response::Unknown(String::new())
+        });
+        assert_eq!(Response::Verdict(Verdict::TooHigh, Some(60)), response);
+
+        // refused locally, so the server is never asked
+        assert!(submit(&client, 1, 1, "500", 2000).is_err());
+        assert!(submit(&client, 1, 1, "400", 1010).is_err());
+
+        assert_eq!(
+            Response::Verdict(Verdict::Correct, None),
+            submit(&client, 1, 1, "400", 2000).unwrap()
+        );
+
+        let requests = server.join().unwrap();
+        assert_eq!(2, requests.len());
+        assert_eq!("level=1&answer=400", requests[1].body);
+
+        let ledger = Ledger::load(&client.cache.user_dir().join("guesses.json")).unwrap();
+        assert_eq!(2, ledger.guesses.len());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
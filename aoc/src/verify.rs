@@ -0,0 +1,275 @@
+use std::error::Error;
+use std::fs;
+
+use aoc_common::{find_input, read_input};
+use serde::Deserialize;
+
+use crate::registry;
+
+#[derive(Debug, PartialEq)]
+pub struct Config {
+    day: Option<usize>,
+}
+
+impl Config {
+    // flags after `aoc verify`. without --day every registered day is checked
+    pub fn build(args: &[String]) -> Result<Config, &'static str> {
+        let mut config = Config { day: None };
+
+        let mut flags = args.iter();
+        while let Some(flag) = flags.next() {
+            match flag.as_str() {
+                "--day" => {
+                    let day = flags.next().ok_or("--day requires a value")?;
+                    let day = day.parse::<usize>().map_err(|_| "--day must be a number")?;
+                    if registry::find(day).is_none() {
+                        return Err("--day does not match a registered day");
+                    }
+                    config.day = Some(day);
+                }
+                _ => return Err(crate::USAGE),
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+// the known answers for a day's input.txt, kept in answers.toml next to it.
+// either part can be left out until it has been solved
+#[derive(Debug, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Expected {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+impl Expected {
+    fn part(&self, part: i32) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            _ => self.part2.as_deref(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Status {
+    Pass,
+    Fail { expected: String, actual: String },
+    Missing,
+    Error(String),
+}
+
+struct Check {
+    day: usize,
+    part: i32,
+    status: Status,
+}
+
+fn answers_path(day: usize) -> String {
+    format!(
+        "{}/../day{:02}/answers.toml",
+        env!("CARGO_MANIFEST_DIR"),
+        day
+    )
+}
+
+// a missing file just means nothing is known yet, but a file that is there
+// and does not parse is an error
+fn read_expected(path: &str) -> Result<Expected, Box<dyn Error>> {
+    match fs::read_to_string(path) {
+        Ok(contents) => toml::from_str(&contents).map_err(|err| format!("{path}: {err}").into()),
+        Err(_) => Ok(Expected::default()),
+    }
+}
+
+pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
+    let days: Vec<&registry::Day> = match config.day {
+        Some(number) => vec![registry::find(number).ok_or("unknown day")?],
+        None => registry::DAYS.iter().collect(),
+    };
+
+    let mut checks = Vec::new();
+    for day in days {
+        let expected = read_expected(&answers_path(day.number))?;
+        let contents = read_input(&find_input(day.number));
+        for part in [1, 2] {
+            let status = match (&contents, expected.part(part)) {
+                (_, None) => Status::Missing,
+                (Ok(contents), answer) => check(day, contents, part, answer),
+                (Err(err), _) => Status::Error(err.to_string()),
+            };
+            checks.push(Check {
+                day: day.number,
+                part,
+                status,
+            });
+        }
+    }
+
+    print!("{}", verify_table(&checks));
+
+    let failed = checks
+        .iter()
+        .filter(|c| matches!(c.status, Status::Fail { .. } | Status::Error(_)))
+        .count();
+    if failed > 0 {
+        return Err(format!("{failed} of {} answers did not verify", checks.len()).into());
+    }
+
+    Ok(())
+}
+
+// parts without a known answer are not run at all, so days that are still
+// being solved (or have no input yet) show up as missing rather than failing
+fn check(day: &registry::Day, contents: &str, part: i32, expected: Option<&str>) -> Status {
+    let Some(expected) = expected else {
+        return Status::Missing;
+    };
+
+    let actual = match (day.solve)(contents, part) {
+        Ok(answer) => answer.to_string(),
+        Err(err) => return Status::Error(err.to_string()),
+    };
+
+    if expected.trim_end() == actual.trim_end() {
+        Status::Pass
+    } else {
+        Status::Fail {
+            expected: expected.trim_end().to_string(),
+            actual: actual.trim_end().to_string(),
+        }
+    }
+}
+
+// failures show both answers, one after the other, so multi-line answers
+// (day10) stay readable
+fn verify_table(checks: &[Check]) -> String {
+    let mut table = "day | part | status\n----+------+--------\n".to_string();
+
+    for c in checks {
+        let status = match &c.status {
+            Status::Pass => "pass".to_string(),
+            Status::Missing => "missing".to_string(),
+            Status::Error(err) => format!("error: {err}"),
+            Status::Fail { expected, actual } => {
+                let mut lines = vec!["FAIL".to_string(), "expected:".to_string()];
+                lines.extend(expected.lines().map(|l| format!("  {l}")));
+                lines.push("actual:".to_string());
+                lines.extend(actual.lines().map(|l| format!("  {l}")));
+                lines.join("\n    |      | ")
+            }
+        };
+        table += &format!("{:>3} | {:>4} | {}\n", c.day, c.part, status);
+    }
+
+    let count = |pred: fn(&Status) -> bool| checks.iter().filter(|c| pred(&c.status)).count();
+    table += &format!(
+        "{} passed, {} failed, {} missing\n",
+        count(|s| *s == Status::Pass),
+        count(|s| matches!(s, Status::Fail { .. } | Status::Error(_))),
+        count(|s| *s == Status::Missing)
+    );
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY06: &str = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+
+    #[test]
+    fn build() {
+        assert_eq!(Config { day: None }, Config::build(&[]).unwrap());
+        assert_eq!(
+            Config { day: Some(4) },
+            Config::build(&["--day".to_string(), "4".to_string()]).unwrap()
+        );
+        assert!(Config::build(&["--all".to_string()]).is_err());
+    }
+
+    #[test]
+    fn expected_from_toml() {
+        let expected: Expected = toml::from_str("part1 = \"7\"\npart2 = \"\"\"\n#.\n.#\n\"\"\"")
+            .unwrap_or_else(|err| {
+                println!("failed to unwrap test result, {err}");
+                Expected::default()
+            });
+
+        assert_eq!(Some("7"), expected.part(1));
+        assert_eq!(Some("#.\n.#\n"), expected.part(2));
+        assert!(toml::from_str::<Expected>("part3 = \"1\"").is_err());
+        assert_eq!(
+            Expected::default(),
+            read_expected("no/such/answers.toml").unwrap()
+        );
+    }
+
+    #[test]
+    fn statuses() {
+        let day = registry::find(6).unwrap();
+
+        assert_eq!(Status::Pass, check(day, DAY06, 1, Some("7")));
+        assert_eq!(Status::Missing, check(day, DAY06, 2, None));
+        assert_eq!(
+            Status::Fail {
+                expected: "8".to_string(),
+                actual: "19".to_string()
+            },
+            check(day, DAY06, 2, Some("8"))
+        );
+        assert!(matches!(check(day, "abc", 1, Some("7")), Status::Error(_)));
+    }
+
+    #[test]
+    fn table() {
+        let checks = vec![
+            Check {
+                day: 6,
+                part: 1,
+                status: Status::Pass,
+            },
+            Check {
+                day: 6,
+                part: 2,
+                status: Status::Fail {
+                    expected: "8".to_string(),
+                    actual: "19".to_string(),
+                },
+            },
+            Check {
+                day: 7,
+                part: 1,
+                status: Status::Missing,
+            },
+        ];
+
+        assert_eq!(
+            "day | part | status
+----+------+--------
+  6 |    1 | pass
+  6 |    2 | FAIL
+    |      | expected:
+    |      |   8
+    |      | actual:
+    |      |   19
+  7 |    1 | missing
+1 passed, 1 failed, 1 missing
+",
+            verify_table(&checks)
+        );
+    }
+
+    // a typo in any committed answers.toml should fail here rather than in
+    // the middle of aoc verify
+    #[test]
+    fn answers_files_parse() {
+        for day in registry::DAYS.iter() {
+            let path = answers_path(day.number);
+            assert!(read_expected(&path).is_ok(), "{path} does not parse");
+        }
+    }
+}
@@ -1,90 +1,116 @@
 use std::error::Error;
-use std::fs;
 
-pub struct Config {
-    file_path: String,
-    top_n: i32,
-}
+pub use aoc_common::Config;
+use aoc_common::{Answer, AocError, Format, Solution};
 
-impl Config {
-    // constructor                            ?? what is 2nd arg here       
-    pub fn build(args: &[String]) -> Result<Config, &'static str> {
-        if args.len() < 3 {
-            // TODO: should make top number optional
-            return Err("must provide input file_path and number to sum")
-        }
+mod stats;
+mod totals;
+pub use stats::{Bucket, Outlier, Percentile, Stats};
+pub use totals::{top_n, ElfTotals};
 
-        // The args variable in main is the owner of the argument values and is 
-        // only letting the parse_config function borrow them, which means we’d 
-        // violate Rust’s borrowing rules if Config tried to take ownership of the 
-        // values in args. This is why we call clone to completely copy the data 
-        // into a new object that Config can own. This is not efficient and uses
-        // more memory than we technically need, but it is logic to follow. This 
-        // way, we don't need to worry about lifetimes of references. 
-        // 
-        // remember -> It’s better to have a working program that’s a bit 
-        // inefficient than to try to hyperoptimize code on your first pass.
-        let file_path = args[1].clone();
-        let top_n = args[2].clone().parse::<i32>().unwrap_or_default();
-
-        Ok(Config { file_path, top_n })
-    }
+const DAY: usize = 1;
+
+pub struct Day01 {
+    pub top_n: usize,
 }
 
-pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(config.file_path)?;
-    let result = solve_top(&contents)?;
-    let result_n = solve_top_n(&contents, config.top_n)?;
-    println!("{result}");
-    println!("{result_n}");
-    Ok(())
+impl Default for Day01 {
+    fn default() -> Self {
+        Day01 { top_n: 3 }
+    }
 }
 
-pub fn solve_top_n(contents: &str, n: i32) -> Result<i32, Box<dyn Error>> {
-    let mut vec = Vec::new();
-    let mut curr = 0;
+impl Solution for Day01 {
+    // one total per elf rather than a copy of the contents
+    type Input = Vec<u64>;
+
+    const OPTIONS: &'static [(&'static str, &'static str)] = &[
+        ("--top N", "how many elves part 2 adds up (default 3)"),
+        ("--leaderboard N", "list the N best stocked elves instead"),
+        (
+            "--stats BUCKETS",
+            "spread and outliers instead, in BUCKETS bars",
+        ),
+    ];
+
+    fn parse(&self, contents: &str) -> Result<Self::Input, AocError> {
+        ElfTotals::new(contents.as_bytes()).collect()
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(sum_top_n(input, 1)?.into())
+    }
 
-    // collect
-    for line in contents.lines() {
-        if line == "" {
-            vec.push(curr);
-            curr = 0;
-            continue;
+    fn part2(&self, input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(sum_top_n(input, self.top_n)?.into())
+    }
+}
+
+pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
+    if let Some(n) = config.option("--leaderboard")? {
+        if config.format != Format::Text {
+            return Err("--leaderboard only prints text".into());
         }
+        return aoc_common::report::<Day01>(DAY, &config, |contents| leaderboard(contents, n));
+    }
 
-        let num: i32 = line.parse::<i32>()?;
-        curr += num;
+    if let Some(buckets) = config.option("--stats")? {
+        return aoc_common::report::<Day01>(DAY, &config, |contents| {
+            let stats = Stats::new(contents, buckets)?;
+            Ok(match config.format {
+                Format::Text => stats.report(),
+                Format::Json => stats.to_json() + "\n",
+            })
+        });
     }
 
-    // sort
-    vec.sort();
+    let top_n = config.option("--top")?.unwrap_or(Day01::default().top_n);
+    aoc_common::run(DAY, &Day01 { top_n }, config)
+}
 
-    let mut i :usize = n as usize;
-    let mut result = 0; 
-    while i > 0 {
-        result += vec[vec.len()-i];
-        i -= 1;
+// one line per elf in the top n, e.g. "1. elf 4  24000"
+pub fn leaderboard(contents: &str, n: usize) -> Result<String, AocError> {
+    let ranked = top_n(ElfTotals::new(contents.as_bytes()), n)?;
+    // the first total is the largest, so the widest
+    let total_width = ranked.first().map(|(_, total)| total.to_string().len());
+    let elf_width = ranked.iter().map(|(elf, _)| elf.to_string().len()).max();
+    let rank_width = n.to_string().len();
+
+    let mut board = String::new();
+    for (rank, (elf, total)) in ranked.iter().enumerate() {
+        board += &format!(
+            "{:>rank_width$}. elf {elf:<ew$}  {total:>tw$}\n",
+            rank + 1,
+            ew = elf_width.unwrap_or(0),
+            tw = total_width.unwrap_or(0),
+        );
     }
-    Ok(result)
+    Ok(board)
 }
 
-pub fn solve_top(contents: &str) -> Result<i32, Box<dyn Error>> {
-    let mut max = 0;
-    let mut curr = 0; 
-    for line in contents.lines() {
-        if line == "" {
-            if curr > max {
-                max = curr;
-            }
-            curr = 0;
-            continue;
-        }
+// the calories carried by the n best stocked elves, added together. it is
+// an error to ask for none, or for more elves than there are
+pub fn solve_top_n(contents: &str, n: usize) -> Result<u64, AocError> {
+    add_up(&top_n(ElfTotals::new(contents.as_bytes()), n)?)
+}
 
-        let num: i32 = line.parse::<i32>()?;
-        curr += num;
-    }
+pub fn solve_top(contents: &str) -> Result<u64, AocError> {
+    solve_top_n(contents, 1)
+}
 
-    Ok(max)
+fn sum_top_n(totals: &[u64], n: usize) -> Result<u64, AocError> {
+    add_up(&top_n(totals.iter().map(|&total| Ok(total)), n)?)
+}
+
+// each total fits in a u64 but several together may not
+fn add_up(ranked: &[(usize, u64)]) -> Result<u64, AocError> {
+    ranked
+        .iter()
+        .try_fold(0_u64, |sum, (_, total)| sum.checked_add(*total))
+        .ok_or_else(|| {
+            let reason = format!("the top {} totals are too large to add up", ranked.len());
+            AocError::unsolvable(DAY, reason)
+        })
 }
 
 #[cfg(test)]
@@ -98,9 +124,80 @@ mod tests {
 
         let result = solve_top(input).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return -1
+            0
         });
 
         assert_eq!(24000, result);
     }
+
+    #[test]
+    fn top_three() {
+        let input = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n\n";
+
+        let result = Day01::default().solve(input, 2).unwrap_or_else(|err| {
+            println!("failed to unwrap test result, {err}");
+            Answer::Int(-1)
+        });
+
+        assert_eq!(Answer::Int(45000), result);
+    }
+
+    #[test]
+    fn top_n_too_large() {
+        let input = "18446744073709551615\n\n18446744073709551615\n";
+        let day = Day01 { top_n: 2 };
+
+        assert_eq!(
+            "day 01: the top 2 totals are too large to add up",
+            day.solve(input, 2).unwrap_err().to_string()
+        );
+        assert!(solve_top_n(input, 2).is_err());
+        assert_eq!(
+            Answer::BigInt(u64::MAX as i128),
+            day.solve(input, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn bad_calories() {
+        let input = "1000\n2000\n\n3x00\n";
+
+        let err = solve_top(input).unwrap_err();
+
+        assert_eq!(
+            "day 01, line 4, column 1: expected a number (found \"3x00\")",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn last_elf_counts() {
+        // no trailing blank line, and the last elf carries the most
+        let input = "1000\n\n2000\n3000";
+
+        assert_eq!(Answer::Int(5000), Day01::default().solve(input, 1).unwrap());
+        assert_eq!(6000, solve_top_n(input, 2).unwrap());
+    }
+
+    #[test]
+    fn too_few_elves() {
+        let err = solve_top_n("1000\n\n2000\n", 3).unwrap_err();
+
+        assert_eq!(
+            "day 01: asked for the top 3 elves but there are only 2",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn prints_leaderboard() {
+        let input = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
+
+        let board = leaderboard(input, 3).unwrap_or_else(|err| {
+            println!("failed to unwrap test result, {err}");
+            String::new()
+        });
+
+        assert_eq!("1. elf 4  24000\n2. elf 3  11000\n3. elf 5  10000\n", board);
+    }
 }
@@ -0,0 +1,264 @@
+use aoc_common::AocError;
+use serde::Serialize;
+
+use crate::{ElfTotals, DAY};
+
+const PERCENTILES: [u32; 5] = [10, 25, 50, 75, 90];
+const BAR_WIDTH: usize = 40;
+
+// a summary of how many calories each elf carries. outliers are the elves
+// more than 1.5 interquartile ranges outside the middle half
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Stats {
+    pub elves: usize,
+    pub total: u64,
+    pub min: u64,
+    pub max: u64,
+    pub mean: f64,
+    pub median: f64,
+    pub percentiles: Vec<Percentile>,
+    pub histogram: Vec<Bucket>,
+    pub outliers: Vec<Outlier>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Percentile {
+    pub percentile: u32,
+    pub calories: f64,
+}
+
+// elves carrying from..=to calories
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Bucket {
+    pub from: u64,
+    pub to: u64,
+    pub elves: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Outlier {
+    pub elf: usize,
+    pub total: u64,
+}
+
+impl Stats {
+    // buckets is how many equal width bars the histogram gets, fewer when
+    // the totals span less calories than that
+    pub fn new(contents: &str, buckets: usize) -> Result<Stats, AocError> {
+        if buckets == 0 {
+            return Err(AocError::unsolvable(
+                DAY,
+                "a histogram needs at least 1 bucket",
+            ));
+        }
+
+        let totals = ElfTotals::new(contents.as_bytes()).collect::<Result<Vec<_>, _>>()?;
+        let mut sorted = totals.clone();
+        sorted.sort_unstable();
+        let (Some(&min), Some(&max)) = (sorted.first(), sorted.last()) else {
+            return Err(AocError::unsolvable(DAY, "there are no elves to report on"));
+        };
+
+        let total = sorted
+            .iter()
+            .try_fold(0_u64, |sum, &t| sum.checked_add(t))
+            .ok_or_else(|| AocError::unsolvable(DAY, "calorie total of every elf is too large"))?;
+        let (q1, q3) = (percentile(&sorted, 25), percentile(&sorted, 75));
+        let fence = 1.5 * (q3 - q1);
+
+        Ok(Stats {
+            elves: sorted.len(),
+            total,
+            min,
+            max,
+            mean: total as f64 / sorted.len() as f64,
+            median: percentile(&sorted, 50),
+            percentiles: PERCENTILES
+                .iter()
+                .map(|&p| Percentile {
+                    percentile: p,
+                    calories: percentile(&sorted, p),
+                })
+                .collect(),
+            histogram: histogram(&sorted, buckets),
+            outliers: totals
+                .iter()
+                .enumerate()
+                .filter(|(_, &t)| (t as f64) < q1 - fence || (t as f64) > q3 + fence)
+                .map(|(i, &total)| Outlier { elf: i + 1, total })
+                .collect(),
+        })
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("stats always serialize")
+    }
+
+    pub fn report(&self) -> String {
+        let mut report = format!("elves        {}\n", self.elves);
+        report += &format!("total        {}\n", self.total);
+        report += &format!("min          {}\n", self.min);
+        report += &format!("max          {}\n", self.max);
+        report += &format!("mean         {:.1}\n", self.mean);
+        report += &format!("median       {:.1}\n", self.median);
+        for p in &self.percentiles {
+            report += &format!("p{:<11} {:.1}\n", p.percentile, p.calories);
+        }
+
+        report += "\nhistogram\n";
+        let width = self.max.to_string().len();
+        let most = self.histogram.iter().map(|b| b.elves).max().unwrap_or(0);
+        for bucket in &self.histogram {
+            let bar = "#".repeat((bucket.elves * BAR_WIDTH).div_ceil(most.max(1)));
+            report += &format!(
+                "  {:>width$} - {:>width$}  {bar:<BAR_WIDTH$}  {}\n",
+                bucket.from, bucket.to, bucket.elves
+            );
+        }
+
+        report += "\noutliers\n";
+        if self.outliers.is_empty() {
+            report += "  none\n";
+        }
+        for outlier in &self.outliers {
+            report += &format!("  elf {} carries {}\n", outlier.elf, outlier.total);
+        }
+        report
+    }
+}
+
+// linear interpolation between the two closest ranks
+fn percentile(sorted: &[u64], p: u32) -> f64 {
+    let rank = (sorted.len() - 1) as f64 * p as f64 / 100.0;
+    let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+    let fraction = rank - lo as f64;
+    sorted[lo] as f64 + (sorted[hi] as f64 - sorted[lo] as f64) * fraction
+}
+
+// worked out in u128, since a span from 0 to u64::MAX holds one more
+// calorie than a u64 can count. every bound still fits in a u64
+fn histogram(sorted: &[u64], buckets: usize) -> Vec<Bucket> {
+    let (min, max) = (sorted[0] as u128, sorted[sorted.len() - 1] as u128);
+    let width = (max - min + 1).div_ceil(buckets as u128);
+
+    let mut histogram: Vec<Bucket> = (0..)
+        .map(|i| min + i * width)
+        .take_while(|&from| from <= max)
+        .map(|from| Bucket {
+            from: from as u64,
+            to: (from + width - 1).min(max) as u64,
+            elves: 0,
+        })
+        .collect();
+    for &total in sorted {
+        histogram[((total as u128 - min) / width) as usize].elves += 1;
+    }
+    histogram
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
+
+    fn stats(input: &str, buckets: usize) -> Stats {
+        Stats::new(input, buckets).expect("test input should have stats")
+    }
+
+    fn buckets(stats: &Stats) -> Vec<(u64, u64, usize)> {
+        stats
+            .histogram
+            .iter()
+            .map(|b| (b.from, b.to, b.elves))
+            .collect()
+    }
+
+    #[test]
+    fn summary() {
+        let stats = stats(EXAMPLE, 2);
+
+        assert_eq!(
+            (5, 55000, 4000, 24000),
+            (stats.elves, stats.total, stats.min, stats.max)
+        );
+        assert_eq!(11000.0, stats.mean);
+        assert_eq!(10000.0, stats.median);
+        assert_eq!(
+            vec![4800.0, 6000.0, 10000.0, 11000.0, 18800.0],
+            stats
+                .percentiles
+                .iter()
+                .map(|p| p.calories)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(vec![(4000, 14000, 4), (14001, 24000, 1)], buckets(&stats));
+        assert_eq!(
+            vec![Outlier {
+                elf: 4,
+                total: 24000
+            }],
+            stats.outliers
+        );
+    }
+
+    #[test]
+    fn narrow_histogram() {
+        // three calories of spread cannot fill ten buckets
+        let spread = stats("1\n\n2\n\n3\n\n3", 10);
+        let single = stats("5", 4);
+
+        assert_eq!(vec![(1, 1, 1), (2, 2, 1), (3, 3, 2)], buckets(&spread));
+        assert_eq!(vec![(5, 5, 1)], buckets(&single));
+    }
+
+    #[test]
+    fn totals_up_to_u64_max() {
+        let stats = stats("0\n\n18446744073709551615\n", 3);
+
+        assert_eq!(
+            vec![
+                (0, 6148914691236517205, 1),
+                (6148914691236517206, 12297829382473034411, 0),
+                (12297829382473034412, u64::MAX, 1),
+            ],
+            buckets(&stats)
+        );
+        assert_eq!(
+            "day 01: calorie total of every elf is too large",
+            Stats::new("1\n\n18446744073709551615\n", 3)
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn report() {
+        let report = stats(EXAMPLE, 2).report();
+
+        assert!(report.starts_with("elves        5\ntotal        55000\n"));
+        assert!(report.contains("median       10000.0\np10          4800.0\n"));
+        assert!(report.contains(&format!("   4000 - 14000  {}  4\n", "#".repeat(40))));
+        assert!(report.contains(&format!("  14001 - 24000  {:<40}  1\n", "#".repeat(10))));
+        assert!(report.ends_with("outliers\n  elf 4 carries 24000\n"));
+    }
+
+    #[test]
+    fn json() {
+        let json = serde_json::to_value(stats(EXAMPLE, 2)).unwrap();
+
+        assert_eq!(11000.0, json["mean"]);
+        assert_eq!(90, json["percentiles"][4]["percentile"]);
+        assert_eq!(24000, json["outliers"][0]["total"]);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            "day 01: there are no elves to report on",
+            Stats::new("\n\n", 3).unwrap_err().to_string()
+        );
+        assert!(Stats::new(EXAMPLE, 0).is_err());
+        assert!(Stats::new("1\nx", 3).is_err());
+    }
+}
@@ -0,0 +1,219 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::io::BufRead;
+
+use aoc_common::{AocError, InputLine};
+
+use crate::DAY;
+
+// per-elf calorie totals, read straight off a BufRead one elf at a time. the
+// same line buffer is reused throughout, so memory use stays flat however big
+// the input is. runs of blank lines count as a single gap between elves
+pub struct ElfTotals<R> {
+    reader: R,
+    buf: String,
+    line: usize,
+    done: bool,
+}
+
+impl<R: BufRead> ElfTotals<R> {
+    pub fn new(reader: R) -> Self {
+        ElfTotals {
+            reader,
+            buf: String::new(),
+            line: 0,
+            done: false,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for ElfTotals<R> {
+    type Item = Result<u64, AocError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let mut total: Option<u64> = None;
+        loop {
+            self.buf.clear();
+            match self.reader.read_line(&mut self.buf) {
+                Ok(0) => {
+                    self.done = true;
+                    return total.map(Ok);
+                }
+                Ok(_) => self.line += 1,
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(AocError::Io {
+                        path: "calorie list".to_string(),
+                        reason: err.to_string(),
+                    }));
+                }
+            }
+
+            let text = self.buf.trim_end_matches(['\n', '\r']);
+            if text.is_empty() {
+                match total {
+                    Some(total) => return Some(Ok(total)),
+                    None => continue,
+                }
+            }
+
+            let line = InputLine::new(DAY, self.line, text);
+            let sum = line.parse_num::<u64>(text).and_then(|n| {
+                n.checked_add(total.unwrap_or(0))
+                    .ok_or_else(|| line.error(text, "calorie total is too large"))
+            });
+            match sum {
+                Ok(sum) => total = Some(sum),
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+    }
+}
+
+// the n best stocked elves as (elf, total), largest total first and ties in
+// elf order. elves count from 1. only n elves are ever held, in a min heap
+// whose smallest is pushed out whenever a bigger one comes along
+pub fn top_n(
+    totals: impl IntoIterator<Item = Result<u64, AocError>>,
+    n: usize,
+) -> Result<Vec<(usize, u64)>, AocError> {
+    if n == 0 {
+        return Err(AocError::unsolvable(DAY, "top n must be at least 1"));
+    }
+
+    let mut heap = BinaryHeap::with_capacity(n + 1);
+    let mut elves = 0;
+    for total in totals {
+        let key = (total?, Reverse(elves + 1));
+        elves += 1;
+        if heap.len() < n {
+            heap.push(Reverse(key));
+        } else if heap.peek().is_some_and(|Reverse(min)| key > *min) {
+            heap.pop();
+            heap.push(Reverse(key));
+        }
+    }
+
+    if elves < n {
+        return Err(AocError::unsolvable(
+            DAY,
+            format!("asked for the top {n} elves but there are only {elves}"),
+        ));
+    }
+
+    Ok(heap
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((total, Reverse(elf)))| (elf, total))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{self, BufReader, Read};
+
+    const EXAMPLE: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
+
+    fn totals(input: &str) -> Vec<u64> {
+        ElfTotals::new(input.as_bytes())
+            .collect::<Result<_, _>>()
+            .expect("test input should have totals")
+    }
+
+    #[test]
+    fn yields_every_elf() {
+        assert_eq!(vec![6000, 4000, 11000, 24000, 10000], totals(EXAMPLE));
+        assert_eq!(vec![1, 2], totals("\n\n1\n\n\n\n2\n\n"));
+        assert_eq!(vec![3], totals("1\r\n2\r\n"));
+        assert!(totals("").is_empty());
+    }
+
+    #[test]
+    fn stops_at_bad_line() {
+        let mut elves = ElfTotals::new("1\n\n2\nx\n\n3".as_bytes());
+
+        assert_eq!(Some(Ok(1)), elves.next());
+        assert_eq!(
+            "day 01, line 4, column 1: expected a number (found \"x\")",
+            elves.next().unwrap().unwrap_err().to_string()
+        );
+        assert_eq!(None, elves.next());
+    }
+
+    #[test]
+    fn bounded_top_n() {
+        let top = |n| top_n(ElfTotals::new(EXAMPLE.as_bytes()), n);
+
+        assert_eq!(vec![(4, 24000), (3, 11000), (5, 10000)], top(3).unwrap());
+        assert_eq!(vec![(4, 24000)], top(1).unwrap());
+        assert_eq!(5, top(5).unwrap().len());
+    }
+
+    #[test]
+    fn ties_keep_elf_order() {
+        assert_eq!(
+            vec![(2, 5), (3, 5)],
+            top_n(ElfTotals::new("1\n\n5\n\n5\n\n5".as_bytes()), 2).unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_bad_n() {
+        let top = |n| top_n(ElfTotals::new(EXAMPLE.as_bytes()), n).unwrap_err();
+
+        assert_eq!("day 01: top n must be at least 1", top(0).to_string());
+        assert_eq!(
+            "day 01: asked for the top 6 elves but there are only 5",
+            top(6).to_string()
+        );
+    }
+
+    // elves made up on the fly, so the whole input never exists at once
+    struct Generated {
+        next: u64,
+        end: u64,
+        pending: Vec<u8>,
+    }
+
+    impl Read for Generated {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.pending.is_empty() {
+                if self.next == self.end {
+                    return Ok(0);
+                }
+                let elf = format!("{}\n{}\n\n", self.next % 977, self.next % 13);
+                self.pending = elf.into_bytes();
+                self.next += 1;
+            }
+
+            let n = buf.len().min(self.pending.len());
+            buf[..n].copy_from_slice(&self.pending[..n]);
+            self.pending.drain(..n);
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn streams_generated_input() {
+        let input = BufReader::new(Generated {
+            next: 0,
+            end: 200_000,
+            pending: Vec::new(),
+        });
+
+        let top: Vec<u64> = top_n(ElfTotals::new(input), 2)
+            .unwrap()
+            .into_iter()
+            .map(|(_, total)| total)
+            .collect();
+        assert_eq!(vec![988, 988], top);
+    }
+}
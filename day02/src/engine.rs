@@ -0,0 +1,251 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Shape {
+    Rock,
+    Paper,
+    Scissors,
+    Lizard,
+    Spock,
+}
+
+impl Shape {
+    // what playing the shape is worth on its own
+    pub fn score(self) -> i32 {
+        match self {
+            Shape::Rock => 1,
+            Shape::Paper => 2,
+            Shape::Scissors => 3,
+            Shape::Lizard => 4,
+            Shape::Spock => 5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+// the second column of the guide read as outcomes, in order from X
+pub const OUTCOMES: [Outcome; 3] = [Outcome::Loss, Outcome::Draw, Outcome::Win];
+
+impl Outcome {
+    pub fn score(self) -> i32 {
+        match self {
+            Outcome::Loss => 0,
+            Outcome::Draw => 3,
+            Outcome::Win => 6,
+        }
+    }
+}
+
+// a game is the shapes in play, in scoring order, and a table of which shape
+// beats which. every pair of different shapes needs exactly one entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    pub shapes: &'static [Shape],
+    pub wins: &'static [(Shape, Shape)],
+}
+
+pub const CLASSIC: Rules = Rules {
+    shapes: &[Shape::Rock, Shape::Paper, Shape::Scissors],
+    wins: &[
+        (Shape::Rock, Shape::Scissors),
+        (Shape::Paper, Shape::Rock),
+        (Shape::Scissors, Shape::Paper),
+    ],
+};
+
+pub const LIZARD_SPOCK: Rules = Rules {
+    shapes: &[
+        Shape::Rock,
+        Shape::Paper,
+        Shape::Scissors,
+        Shape::Lizard,
+        Shape::Spock,
+    ],
+    wins: &[
+        (Shape::Rock, Shape::Scissors),
+        (Shape::Rock, Shape::Lizard),
+        (Shape::Paper, Shape::Rock),
+        (Shape::Paper, Shape::Spock),
+        (Shape::Scissors, Shape::Paper),
+        (Shape::Scissors, Shape::Lizard),
+        (Shape::Lizard, Shape::Paper),
+        (Shape::Lizard, Shape::Spock),
+        (Shape::Spock, Shape::Rock),
+        (Shape::Spock, Shape::Scissors),
+    ],
+};
+
+impl Default for Rules {
+    fn default() -> Self {
+        CLASSIC
+    }
+}
+
+impl FromStr for Rules {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "classic" => Ok(CLASSIC),
+            "lizard-spock" => Ok(LIZARD_SPOCK),
+            _ => Err(format!("unknown rules {s:?}")),
+        }
+    }
+}
+
+impl Rules {
+    pub fn beats(&self, shape: Shape, other: Shape) -> bool {
+        self.wins.contains(&(shape, other))
+    }
+
+    pub fn loses_to(&self, shape: Shape, other: Shape) -> bool {
+        self.beats(other, shape)
+    }
+
+    // how playing shape against theirs turns out
+    pub fn outcome(&self, shape: Shape, theirs: Shape) -> Outcome {
+        if self.beats(shape, theirs) {
+            Outcome::Win
+        } else if self.loses_to(shape, theirs) {
+            Outcome::Loss
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    // the shape to play against theirs for the outcome wanted. when several
+    // would do, as in lizard-spock, the first in scoring order is picked
+    pub fn respond(&self, theirs: Shape, wanted: Outcome) -> Option<Shape> {
+        self.shapes
+            .iter()
+            .copied()
+            .find(|&shape| self.outcome(shape, theirs) == wanted)
+    }
+
+    pub fn score(&self, shape: Shape, theirs: Shape) -> i32 {
+        shape.score() + self.outcome(shape, theirs).score()
+    }
+
+    // opponents play A, B, C... in scoring order
+    pub fn opponent(&self, letter: &str) -> Option<Shape> {
+        match letter.as_bytes() {
+            [b] => self.shapes.get(b.checked_sub(b'A')? as usize).copied(),
+            _ => None,
+        }
+    }
+}
+
+// the letters of the guide's second column end at Z, so there are X, Y, Z
+// for three choices and V to Z for five
+pub fn ours_letter(index: usize, count: usize) -> char {
+    (b'Z' + 1 - count as u8 + index as u8) as char
+}
+
+// what a second column letter stands for, given the choices in letter order
+pub fn ours<T: Copy>(choices: &[T], letter: &str) -> Option<T> {
+    match letter.as_bytes() {
+        [b] => choices
+            .get(b.checked_sub(ours_letter(0, choices.len()) as u8)? as usize)
+            .copied(),
+        _ => None,
+    }
+}
+
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Shape::Rock => "rock",
+            Shape::Paper => "paper",
+            Shape::Scissors => "scissors",
+            Shape::Lizard => "lizard",
+            Shape::Spock => "spock",
+        };
+        f.pad(name)
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Outcome::Loss => "loss",
+            Outcome::Draw => "draw",
+            Outcome::Win => "win",
+        };
+        f.pad(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classic_scores() {
+        // the old hard coded table, opponent down and ours across
+        let expected = [[4, 8, 3], [1, 5, 9], [7, 2, 6]];
+
+        for (theirs, row) in CLASSIC.shapes.iter().zip(expected) {
+            for (shape, score) in CLASSIC.shapes.iter().zip(row) {
+                assert_eq!(score, CLASSIC.score(*shape, *theirs));
+            }
+        }
+    }
+
+    #[test]
+    fn tables_are_complete() {
+        for rules in [CLASSIC, LIZARD_SPOCK] {
+            for &a in rules.shapes {
+                for &b in rules.shapes {
+                    let wins = [rules.beats(a, b), rules.beats(b, a)];
+                    // exactly one of two different shapes wins
+                    assert_eq!(usize::from(a != b), wins.iter().filter(|w| **w).count());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn respond() {
+        assert_eq!(
+            Some(Shape::Rock),
+            CLASSIC.respond(Shape::Rock, Outcome::Draw)
+        );
+        assert_eq!(
+            Some(Shape::Paper),
+            CLASSIC.respond(Shape::Rock, Outcome::Win)
+        );
+        assert_eq!(
+            Some(Shape::Paper),
+            LIZARD_SPOCK.respond(Shape::Spock, Outcome::Win)
+        );
+        assert_eq!(
+            Some(Shape::Rock),
+            LIZARD_SPOCK.respond(Shape::Spock, Outcome::Loss)
+        );
+        assert_eq!(None, CLASSIC.respond(Shape::Spock, Outcome::Win));
+    }
+
+    #[test]
+    fn letters() {
+        assert_eq!(Some(Shape::Scissors), CLASSIC.opponent("C"));
+        assert_eq!(None, CLASSIC.opponent("D"));
+        assert_eq!(Some(Shape::Rock), ours(CLASSIC.shapes, "X"));
+        assert_eq!(None, ours(CLASSIC.shapes, "W"));
+        assert_eq!(Some(Shape::Rock), ours(LIZARD_SPOCK.shapes, "V"));
+        assert_eq!(Some(Shape::Spock), LIZARD_SPOCK.opponent("E"));
+        assert_eq!(None, ours(CLASSIC.shapes, "XY"));
+        assert_eq!(Some(Outcome::Win), ours(&OUTCOMES, "Z"));
+        assert_eq!('V', ours_letter(0, 5));
+    }
+}
@@ -0,0 +1,196 @@
+use std::str::FromStr;
+
+use aoc_common::{input_lines, AocError, InputLine};
+
+use crate::{ours, ours_letter, Day02, Round, Shape, DAY};
+
+// how malformed lines are handled. strict stops at the first one, lenient
+// skips them and keeps count
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    #[default]
+    Strict,
+    Lenient,
+}
+
+impl FromStr for Mode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "strict" => Ok(Mode::Strict),
+            "lenient" => Ok(Mode::Lenient),
+            _ => Err(format!("unknown mode {s:?}")),
+        }
+    }
+}
+
+// one line of the strategy guide: the opponent's shape, and the second
+// column letter that each part reads differently
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    pub line: usize,
+    pub theirs: Shape,
+    pub letter: char,
+}
+
+impl Entry {
+    // what the second column letter picks out of choices, read from X up
+    pub fn choice<T: Copy>(&self, choices: &[T]) -> Result<T, AocError> {
+        ours(choices, &self.letter.to_string()).ok_or_else(|| self.error("invalid move"))
+    }
+
+    // the letter always sits in column 3 of a line that parsed
+    pub fn error(&self, reason: &str) -> AocError {
+        AocError::Parse {
+            day: DAY,
+            line: self.line,
+            column: 3,
+            text: self.letter.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guide {
+    pub entries: Vec<Entry>,
+    // malformed lines passed over in lenient mode
+    pub skipped: usize,
+}
+
+impl Guide {
+    // lines skipped while parsing, plus the entries left out of rounds
+    // because their letter had no reading
+    pub fn skipped_with(&self, rounds: &[Round]) -> usize {
+        self.skipped + self.entries.len() - rounds.len()
+    }
+}
+
+impl Day02 {
+    pub fn parse_guide(&self, contents: &str) -> Result<Guide, AocError> {
+        let mut guide = Guide {
+            entries: Vec::new(),
+            skipped: 0,
+        };
+
+        for line in input_lines(DAY, contents) {
+            match (self.parse_line(line), self.mode) {
+                (Ok(entry), _) => guide.entries.push(entry),
+                (Err(_), Mode::Lenient) => guide.skipped += 1,
+                (Err(err), Mode::Strict) => return Err(err),
+            }
+        }
+
+        Ok(guide)
+    }
+
+    // exactly "<opponent> <ours>", a single space apart with nothing after
+    fn parse_line(&self, line: InputLine) -> Result<Entry, AocError> {
+        let text = line.text.trim_end();
+        if text.len() < line.text.len() {
+            return Err(line.error(&line.text[text.len()..], "trailing whitespace"));
+        }
+
+        let mut moves = text.split(' ');
+        let (Some(theirs), Some(letter)) = (moves.next(), moves.next()) else {
+            return Err(line.error(line.text, "line does not contain 2 moves"));
+        };
+        if let Some(extra) = moves.next() {
+            return Err(line.error(extra, "unexpected token after 2 moves"));
+        }
+
+        let theirs = self
+            .rules
+            .opponent(theirs)
+            .ok_or_else(|| line.error(theirs, "invalid move"))?;
+        let letters = self.rules.shapes.len();
+        let letter = match letter.as_bytes() {
+            [b] if ours(self.rules.shapes, letter).is_some() => *b as char,
+            _ => {
+                let expected = format!(
+                    "invalid move, expected {} to {}",
+                    ours_letter(0, letters),
+                    ours_letter(letters - 1, letters)
+                );
+                return Err(line.error(letter, expected));
+            }
+        };
+
+        Ok(Entry {
+            line: line.line,
+            theirs,
+            letter,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(input: &str) -> String {
+        match Day02::default().parse_guide(input) {
+            Ok(guide) => format!("parsed {} entries", guide.entries.len()),
+            Err(err) => err.to_string(),
+        }
+    }
+
+    #[test]
+    fn strict() {
+        let guide = Day02::default().parse_guide("A Y\nC Z\n").unwrap();
+
+        assert_eq!(
+            Entry {
+                line: 2,
+                theirs: Shape::Scissors,
+                letter: 'Z'
+            },
+            guide.entries[1]
+        );
+        assert_eq!(0, guide.skipped);
+    }
+
+    #[test]
+    fn malformed_lines() {
+        assert_eq!(
+            "day 02, line 2, column 4: trailing whitespace (found \" \")",
+            error("A Y\nB X \n")
+        );
+        assert_eq!(
+            "day 02, line 1, column 5: unexpected token after 2 moves (found \"Z\")",
+            error("A Y Z\n")
+        );
+        assert_eq!(
+            "day 02, line 1, column 1: line does not contain 2 moves (found \"AY\")",
+            error("AY\n")
+        );
+        assert_eq!(
+            "day 02, line 1, column 1: invalid move (found \"D\")",
+            error("D Y\n")
+        );
+        assert_eq!(
+            "day 02, line 1, column 3: invalid move, expected X to Z (found \"W\")",
+            error("A W\n")
+        );
+        assert_eq!(
+            "day 02, line 2, column 1: line does not contain 2 moves (found \"\")",
+            error("A Y\n\nB X\n")
+        );
+    }
+
+    #[test]
+    fn lenient() {
+        let day = Day02 {
+            mode: Mode::Lenient,
+            ..Default::default()
+        };
+        let guide = day.parse_guide("A Y\nB X \nnonsense\nC Z\n\n").unwrap();
+
+        assert_eq!(
+            vec![1, 4],
+            guide.entries.iter().map(|e| e.line).collect::<Vec<_>>()
+        );
+        assert_eq!(3, guide.skipped);
+    }
+}
@@ -1,130 +1,148 @@
-use std::fs;
 use std::error::Error;
-use std::collections::HashMap;
 
-pub struct Config {
-    file_path: String,
-    problem_number: i32,
-}
+pub use aoc_common::Config;
+use aoc_common::{Answer, AocError, Format, Solution};
+use serde::Serialize;
 
-impl Config {
-    pub fn build(args: &[String]) -> Result<Config, &'static str> {
-        if args.len() < 3 {
-            return Err("must provide input file_path and which problem to solve")
-        }
-        let file_path = args[1].clone();
-        let problem_number = args[2].clone().parse::<i32>().unwrap_or_default();
+mod engine;
+mod guide;
+mod replay;
+mod search;
+pub use engine::{ours, ours_letter, Outcome, Rules, Shape, CLASSIC, LIZARD_SPOCK, OUTCOMES};
+pub use guide::{Entry, Guide, Mode};
+pub use replay::{Replay, ShapeSummary};
+pub use search::{Column, Interpretation, Mapping, Search};
 
-        Ok(Config { file_path, problem_number })
-    }
-}
+const DAY: usize = 2;
 
 pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(config.file_path)?;
-    let result = solve(&contents, &config.problem_number)?;
-    println!("{result}");
-    Ok(())
-}
-
-fn solve(contents: &str, problem_number: &i32) -> Result<i32, Box<dyn Error>> {
-    let mut score = 0;
-
-    let line_scorer: fn(line: &str) -> Result<i32, Box<dyn Error>> = match problem_number {
-        1 => initial_line_score,
-        2 => correct_line_score,
-        _ => Err("must select problem 1 or 2")?
+    let day = Day02 {
+        rules: config.option("--rules")?.unwrap_or_default(),
+        mode: config.option("--parse")?.unwrap_or_default(),
     };
 
-    for line in contents.lines() {
-        score += line_scorer(line)?;
-    }
-    
-    Ok(score)
-}
-
-fn correct_line_score(line: &str) -> Result<i32, Box<dyn Error>> {
-    let moves = line.split(" ").collect::<Vec<&str>>();
-    if moves.len() < 2 {
-        Err("line does not contain 2 moves")?
+    if let Some(column) = config.option("--search")? {
+        if config.format != Format::Text {
+            return Err("--search only prints text".into());
+        }
+        return aoc_common::report::<Day02>(DAY, &config, |contents| {
+            Ok(day.search(contents, column)?.report())
+        });
     }
 
-    let mut local_score = 0;
-
-    match moves[0] {
-        "A" => {
-            match moves[1] {
-                "X" => local_score += 3,
-                "Y" => local_score += 4,
-                "Z" => local_score += 8,
-                _ => {}
-            }
-        }
-        "B" => {
-            match moves[1] {
-                "X" => local_score += 1,
-                "Y" => local_score += 5,
-                "Z" => local_score += 9,
-                _ => {}
-            }
-        }
-        "C" => {
-            match moves[1] {
-                "X" => local_score += 2,
-                "Y" => local_score += 6,
-                "Z" => local_score += 7,
-                _ => {}
-            }
-        }
-        _ => {}
+    if let Some(column) = config.option("--replay")? {
+        return aoc_common::report::<Day02>(DAY, &config, |contents| {
+            let replay = day.replay(contents, column)?;
+            Ok(match config.format {
+                Format::Text => replay.report(),
+                Format::Json => replay.to_json() + "\n",
+            })
+        });
     }
 
-    Ok(local_score)
+    aoc_common::run(DAY, &day, config)
 }
 
-fn initial_line_score(line: &str) -> Result<i32, Box<dyn Error>> {
-    let shape_value = HashMap::from([ ("X", 1), ("A", 1), ("Y", 2), ("B", 2), ("Z", 3), ("C", 3) ]);
+#[derive(Default)]
+pub struct Day02 {
+    pub rules: Rules,
+    pub mode: Mode,
+}
 
+impl Solution for Day02 {
+    type Input = Guide;
+
+    const OPTIONS: &'static [(&'static str, &'static str)] = &[
+        ("--rules NAME", "classic or lizard-spock (default classic)"),
+        (
+            "--parse MODE",
+            "strict, or lenient to skip malformed lines (default strict)",
+        ),
+        (
+            "--search COLUMN",
+            "score every reading of shapes or outcomes instead",
+        ),
+        (
+            "--replay COLUMN",
+            "list every round with the guide read as shapes or outcomes",
+        ),
+    ];
+
+    fn parse(&self, contents: &str) -> Result<Self::Input, AocError> {
+        self.parse_guide(contents)
+    }
 
-    let moves = line.split(" ").collect::<Vec<&str>>();
-    if moves.len() < 2 {
-        Err("line does not contain 2 moves")?
+    fn part1(&self, input: &Self::Input) -> Result<Answer, AocError> {
+        let rounds = self.rounds(input, &Mapping::as_written(Column::Shapes, &self.rules))?;
+        Ok(rounds.iter().map(|r| r.score).sum::<i32>().into())
     }
 
-    let mut local_score = 0;
-    match shape_value.get(moves[1]) {
-        Some(value) => local_score += value,
-        None => Err("invalid move {moves[1]}")?
+    fn part2(&self, input: &Self::Input) -> Result<Answer, AocError> {
+        let rounds = self.rounds(input, &Mapping::as_written(Column::Outcomes, &self.rules))?;
+        Ok(rounds.iter().map(|r| r.score).sum::<i32>().into())
     }
+}
 
+// one scored line of the strategy guide. score is the shape points plus
+// the outcome points
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Round {
+    pub line: usize,
+    pub theirs: Shape,
+    pub ours: Shape,
+    pub outcome: Outcome,
+    pub shape_points: i32,
+    pub outcome_points: i32,
+    pub score: i32,
+}
 
-    // AX rock, BY paper, CZ scissors
-    match moves[0] {
-        "A" => {
-            match moves[1] {
-                "X" => local_score += 3,
-                "Y" => local_score += 6,
-                _ => {}
-            }
-        }
-        "B" => {
-            match moves[1] {
-                "Z" => local_score += 6,
-                "Y" => local_score += 3,
-                _ => {}
-            }
+impl Day02 {
+    fn round(&self, entry: &Entry, ours: Shape) -> Round {
+        let outcome = self.rules.outcome(ours, entry.theirs);
+        Round {
+            line: entry.line,
+            theirs: entry.theirs,
+            ours,
+            outcome,
+            shape_points: ours.score(),
+            outcome_points: outcome.score(),
+            score: self.rules.score(ours, entry.theirs),
         }
-        "C" => {
-            match moves[1] {
-                "X" => local_score += 6,
-                "Z" => local_score += 3,
-                _ => {}
+    }
 
+    // every entry of the guide, scored with its second column read through
+    // mapping. a letter the mapping cannot read is a malformed line like any
+    // other, so lenient mode leaves that round out, see Guide::skipped_with
+    pub fn rounds(&self, guide: &Guide, mapping: &Mapping) -> Result<Vec<Round>, AocError> {
+        let mut rounds = Vec::new();
+        for entry in &guide.entries {
+            let round = match mapping {
+                Mapping::Shapes(shapes) => self.initial_line_score(entry, shapes),
+                Mapping::Outcomes(outcomes) => self.correct_line_score(entry, outcomes),
+            };
+            match (round, self.mode) {
+                (Ok(round), _) => rounds.push(round),
+                (Err(_), Mode::Lenient) => {}
+                (Err(err), Mode::Strict) => return Err(err),
             }
         }
-        _ => Err("invalid move {moves[0]}")?
+        Ok(rounds)
+    }
+
+    // the second column is the shape to play, read through shapes
+    fn initial_line_score(&self, entry: &Entry, shapes: &[Shape]) -> Result<Round, AocError> {
+        Ok(self.round(entry, entry.choice(shapes)?))
     }
 
-    Ok(local_score)
+    // the second column is how the round has to end, read through outcomes
+    fn correct_line_score(&self, entry: &Entry, outcomes: &[Outcome]) -> Result<Round, AocError> {
+        let ours = self
+            .rules
+            .respond(entry.theirs, entry.choice(outcomes)?)
+            .ok_or_else(|| entry.error("no shape ends the round that way"))?;
+
+        Ok(self.round(entry, ours))
+    }
 }
 
 #[cfg(test)]
@@ -133,27 +151,84 @@ mod tests {
 
     #[test]
     fn p1() {
-        let input = "A Y \nB X \nC Z \n";
+        let input = "A Y\nB X\nC Z\n";
 
         let p = 1;
-        let result = solve(input, &p).unwrap_or_else(|err| {
+        let result = Day02::default().solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return -1
+            Answer::Int(-1)
         });
 
-        assert_eq!(15, result);
+        assert_eq!(Answer::Int(15), result);
     }
 
     #[test]
     fn p2() {
-        let input = "A Y \nB X \nC Z \n";
+        let input = "A Y\nB X\nC Z\n";
 
         let p = 2;
-        let result = solve(input, &p).unwrap_or_else(|err| {
+        let result = Day02::default().solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return -1
+            Answer::Int(-1)
         });
 
-        assert_eq!(12, result);
+        assert_eq!(Answer::Int(12), result);
+    }
+
+    #[test]
+    fn invalid_move() {
+        let input = "A Y\nB Q\nC Z\n";
+
+        let err = Day02::default().solve(input, 2).unwrap_err();
+
+        assert_eq!(
+            "day 02, line 2, column 3: invalid move, expected X to Z (found \"Q\")",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn lizard_spock() {
+        // spock vaporizes rock, then paper disproves spock
+        let input = "A Z\nE W\n";
+        let day = Day02 {
+            rules: LIZARD_SPOCK,
+            ..Default::default()
+        };
+
+        assert_eq!(Answer::Int(5 + 6 + 2 + 6), day.solve(input, 1).unwrap());
+        assert!(Day02::default().solve("E Z\n", 1).is_err());
+    }
+
+    #[test]
+    fn lenient_skips_bad_lines() {
+        let input = "A Y\nB Q\nC Z \nC Z\n";
+        let day = Day02 {
+            mode: Mode::Lenient,
+            ..Default::default()
+        };
+
+        assert_eq!(Answer::Int(8 + 6), day.solve(input, 1).unwrap());
+        assert!(Day02::default().solve(input, 1).is_err());
+    }
+
+    #[test]
+    fn lenient_skips_letters_a_part_cannot_read() {
+        // V is a shape under lizard-spock, but there is no fourth outcome
+        let input = "A Y\nE V\n";
+        let day = Day02 {
+            rules: LIZARD_SPOCK,
+            mode: Mode::Lenient,
+        };
+
+        assert_eq!(Answer::Int(1 + 3), day.solve(input, 2).unwrap());
+        assert_eq!(1, day.replay(input, Column::Outcomes).unwrap().skipped);
+        assert_eq!(0, day.replay(input, Column::Shapes).unwrap().skipped);
+
+        let strict = Day02 {
+            rules: LIZARD_SPOCK,
+            ..Default::default()
+        };
+        assert!(strict.solve(input, 2).is_err());
     }
 }
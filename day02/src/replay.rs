@@ -0,0 +1,173 @@
+use aoc_common::AocError;
+use serde::Serialize;
+
+use crate::search::skipped_note;
+use crate::{Column, Day02, Mapping, Outcome, Round, Shape};
+
+// the guide played back round by round, with how each of our shapes fared
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Replay {
+    pub mapping: String,
+    pub rounds: Vec<Round>,
+    pub shapes: Vec<ShapeSummary>,
+    pub total: i32,
+    pub skipped: usize,
+}
+
+// the rounds we played one shape in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ShapeSummary {
+    pub shape: Shape,
+    pub played: usize,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    pub points: i32,
+}
+
+impl Day02 {
+    pub fn replay(&self, contents: &str, column: Column) -> Result<Replay, AocError> {
+        let mapping = Mapping::as_written(column, &self.rules);
+        let guide = self.parse_guide(contents)?;
+        let rounds = self.rounds(&guide, &mapping)?;
+
+        let shapes = self
+            .rules
+            .shapes
+            .iter()
+            .map(|&shape| {
+                let played: Vec<&Round> = rounds.iter().filter(|r| r.ours == shape).collect();
+                let count = |outcome| played.iter().filter(|r| r.outcome == outcome).count();
+                ShapeSummary {
+                    shape,
+                    played: played.len(),
+                    wins: count(Outcome::Win),
+                    draws: count(Outcome::Draw),
+                    losses: count(Outcome::Loss),
+                    points: played.iter().map(|r| r.score).sum(),
+                }
+            })
+            .collect();
+
+        Ok(Replay {
+            mapping: mapping.to_string(),
+            total: rounds.iter().map(|r| r.score).sum(),
+            skipped: guide.skipped_with(&rounds),
+            rounds,
+            shapes,
+        })
+    }
+}
+
+impl Replay {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("replays always serialize")
+    }
+
+    pub fn report(&self) -> String {
+        let mut report = skipped_note(self.skipped);
+        report += &format!("reading {}\n\n", self.mapping);
+
+        report += " line  theirs    ours      outcome  shape  outcome  score\n";
+        for r in &self.rounds {
+            report += &format!(
+                "{:>5}  {:<8}  {:<8}  {:<7}  {:>5}  {:>7}  {:>5}\n",
+                r.line, r.theirs, r.ours, r.outcome, r.shape_points, r.outcome_points, r.score
+            );
+        }
+
+        report += "\nshape     played  wins  draws  losses  points\n";
+        for s in &self.shapes {
+            report += &format!(
+                "{:<8}  {:>6}  {:>4}  {:>5}  {:>6}  {:>6}\n",
+                s.shape, s.played, s.wins, s.draws, s.losses, s.points
+            );
+        }
+        report += &format!("\ntotal {}\n", self.total);
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "A Y\nB X\nC Z\n";
+
+    fn replay(column: Column) -> Replay {
+        Day02::default()
+            .replay(EXAMPLE, column)
+            .expect("the example should replay")
+    }
+
+    #[test]
+    fn rounds() {
+        let replay = replay(Column::Shapes);
+
+        assert_eq!(15, replay.total);
+        assert_eq!(
+            Round {
+                line: 1,
+                theirs: Shape::Rock,
+                ours: Shape::Paper,
+                outcome: Outcome::Win,
+                shape_points: 2,
+                outcome_points: 6,
+                score: 8,
+            },
+            replay.rounds[0]
+        );
+        assert_eq!(
+            vec![8, 1, 6],
+            replay.rounds.iter().map(|r| r.score).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn by_shape() {
+        let replay = replay(Column::Outcomes);
+
+        // every outcome the example asks for is reached by playing rock
+        let rock = replay.shapes[0];
+        assert_eq!(
+            (3, 1, 1, 1, 4 + 1 + 7),
+            (rock.played, rock.wins, rock.draws, rock.losses, rock.points)
+        );
+        assert_eq!(0, replay.shapes[2].played);
+        assert_eq!(12, replay.shapes.iter().map(|s| s.points).sum::<i32>());
+    }
+
+    #[test]
+    fn report() {
+        let report = replay(Column::Shapes).report();
+
+        assert!(report.starts_with("reading X=rock Y=paper Z=scissors\n"));
+        assert!(report.contains("    1  rock      paper     win          2        6      8\n"));
+        assert!(report.contains("paper          1     1      0       0       8\n"));
+        assert!(report.ends_with("total 15\n"));
+    }
+
+    #[test]
+    fn json() {
+        let json = serde_json::to_value(replay(Column::Shapes)).unwrap();
+
+        assert_eq!("paper", json["rounds"][0]["ours"]);
+        assert_eq!("win", json["rounds"][0]["outcome"]);
+        assert_eq!(6, json["rounds"][0]["outcome_points"]);
+        assert_eq!(1, json["shapes"][1]["wins"]);
+    }
+
+    #[test]
+    fn counts_skipped() {
+        let day = Day02 {
+            mode: crate::Mode::Lenient,
+            ..Default::default()
+        };
+        let replay = day.replay("A Y\nA\nB X\n", Column::Shapes).unwrap();
+
+        assert_eq!((2, 1), (replay.rounds.len(), replay.skipped));
+        assert!(replay
+            .report()
+            .starts_with("skipped 1 malformed line\n\nreading"));
+    }
+}
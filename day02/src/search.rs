@@ -0,0 +1,245 @@
+use std::cmp::Reverse;
+use std::fmt;
+use std::str::FromStr;
+
+use aoc_common::AocError;
+
+use crate::{ours_letter, Day02, Outcome, Round, Rules, Shape, OUTCOMES};
+
+// which reading of the guide's second column to search over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    Shapes,
+    Outcomes,
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "shapes" => Ok(Column::Shapes),
+            "outcomes" => Ok(Column::Outcomes),
+            _ => Err(format!("unknown column {s:?}")),
+        }
+    }
+}
+
+// what each second column letter stands for, in letter order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mapping {
+    Shapes(Vec<Shape>),
+    Outcomes(Vec<Outcome>),
+}
+
+impl Mapping {
+    // the guide read the way each part of the puzzle reads it
+    pub fn as_written(column: Column, rules: &Rules) -> Mapping {
+        match column {
+            Column::Shapes => Mapping::Shapes(rules.shapes.to_vec()),
+            Column::Outcomes => Mapping::Outcomes(OUTCOMES.to_vec()),
+        }
+    }
+}
+
+impl fmt::Display for Mapping {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names: Vec<String> = match self {
+            Mapping::Shapes(shapes) => shapes.iter().map(|s| s.to_string()).collect(),
+            Mapping::Outcomes(outcomes) => outcomes.iter().map(|o| o.to_string()).collect(),
+        };
+        let letters: Vec<String> = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| format!("{}={name}", ours_letter(i, names.len())))
+            .collect();
+        f.pad(&letters.join(" "))
+    }
+}
+
+// the guide scored under one mapping
+#[derive(Debug, Clone, PartialEq)]
+pub struct Interpretation {
+    pub mapping: Mapping,
+    pub rounds: Vec<Round>,
+    pub total: i32,
+}
+
+// every interpretation, highest total first. ties keep the order mappings
+// were tried in, which starts with the guide as written
+#[derive(Debug, Clone, PartialEq)]
+pub struct Search {
+    pub interpretations: Vec<Interpretation>,
+    pub skipped: usize,
+}
+
+impl Day02 {
+    pub fn search(&self, contents: &str, column: Column) -> Result<Search, AocError> {
+        let mappings: Vec<Mapping> = match column {
+            Column::Shapes => permutations(self.rules.shapes)
+                .into_iter()
+                .map(Mapping::Shapes)
+                .collect(),
+            Column::Outcomes => permutations(&OUTCOMES)
+                .into_iter()
+                .map(Mapping::Outcomes)
+                .collect(),
+        };
+
+        let guide = self.parse_guide(contents)?;
+        let mut interpretations = Vec::new();
+        // every mapping of a column reads the same letters, so they all
+        // leave out the same entries
+        let mut skipped = guide.skipped;
+        for mapping in mappings {
+            let rounds = self.rounds(&guide, &mapping)?;
+            skipped = guide.skipped_with(&rounds);
+            interpretations.push(Interpretation {
+                total: rounds.iter().map(|r| r.score).sum(),
+                mapping,
+                rounds,
+            });
+        }
+        interpretations.sort_by_key(|i| Reverse(i.total));
+
+        Ok(Search {
+            interpretations,
+            skipped,
+        })
+    }
+}
+
+impl Search {
+    pub fn best(&self) -> &Interpretation {
+        &self.interpretations[0]
+    }
+
+    pub fn worst(&self) -> &Interpretation {
+        &self.interpretations[self.interpretations.len() - 1]
+    }
+
+    // every mapping's total, then the best and worst side by side per round
+    pub fn report(&self) -> String {
+        let (best, worst) = (self.best(), self.worst());
+        let width = best.mapping.to_string().len();
+
+        let mut report = skipped_note(self.skipped);
+        report += &format!("{:width$}  total\n", "mapping");
+        for (i, interpretation) in self.interpretations.iter().enumerate() {
+            let note = match i {
+                0 => "  best",
+                _ if i == self.interpretations.len() - 1 => "  worst",
+                _ => "",
+            };
+            report += &format!(
+                "{:width$}  {:>5}{note}\n",
+                interpretation.mapping, interpretation.total
+            );
+        }
+
+        report += "\n line  theirs    best            worst\n";
+        for (b, w) in best.rounds.iter().zip(&worst.rounds) {
+            report += &format!(
+                "{:>5}  {:<8}  {:<8}  {:>4}  {:<8}  {:>4}\n",
+                b.line, b.theirs, b.ours, b.score, w.ours, w.score
+            );
+        }
+        report
+    }
+}
+
+// a heading for reports on a leniently parsed guide
+pub(crate) fn skipped_note(skipped: usize) -> String {
+    match skipped {
+        0 => String::new(),
+        1 => "skipped 1 malformed line\n\n".to_string(),
+        n => format!("skipped {n} malformed lines\n\n"),
+    }
+}
+
+// every ordering of items, starting with items as given
+fn permutations<T: Copy>(items: &[T]) -> Vec<Vec<T>> {
+    if items.len() <= 1 {
+        return vec![items.to_vec()];
+    }
+
+    let mut all = Vec::new();
+    for (i, &first) in items.iter().enumerate() {
+        let mut rest = items.to_vec();
+        rest.remove(i);
+        for mut tail in permutations(&rest) {
+            tail.insert(0, first);
+            all.push(tail);
+        }
+    }
+    all
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LIZARD_SPOCK;
+
+    const EXAMPLE: &str = "A Y\nB X\nC Z\n";
+
+    fn search(rules: Rules, column: Column) -> Search {
+        Day02 {
+            rules,
+            ..Default::default()
+        }
+        .search(EXAMPLE, column)
+        .expect("the example should be searchable")
+    }
+
+    #[test]
+    fn all_orderings() {
+        assert_eq!(6, permutations(&[1, 2, 3]).len());
+        assert_eq!(vec![1, 2, 3], permutations(&[1, 2, 3])[0]);
+        assert_eq!(120, permutations(LIZARD_SPOCK.shapes).len());
+    }
+
+    #[test]
+    fn search_shapes() {
+        let search = search(Rules::default(), Column::Shapes);
+
+        assert_eq!(6, search.interpretations.len());
+        // always playing the winning shape: 6+2, 6+3, 6+1
+        assert_eq!(
+            Mapping::Shapes(vec![Shape::Scissors, Shape::Paper, Shape::Rock]),
+            search.best().mapping
+        );
+        assert_eq!(24, search.best().total);
+        assert_eq!(6, search.worst().total);
+        assert_eq!(
+            vec![8, 9, 7],
+            search
+                .best()
+                .rounds
+                .iter()
+                .map(|r| r.score)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn search_outcomes() {
+        let search = search(Rules::default(), Column::Outcomes);
+
+        // the as written reading scores 12, but losing to rock, beating
+        // paper and drawing with scissors does better
+        assert_eq!(6, search.interpretations.len());
+        assert_eq!("X=win Y=loss Z=draw", search.best().mapping.to_string());
+        assert_eq!(3 + 9 + 6, search.best().total);
+        assert_eq!(4 + 1 + 7, search.worst().total);
+    }
+
+    #[test]
+    fn report() {
+        let report = search(Rules::default(), Column::Shapes).report();
+
+        assert!(report.starts_with(&format!("{:25}  total\n", "mapping")));
+        assert!(report.contains("X=scissors Y=paper Z=rock     24  best\n"));
+        assert!(report.contains("X=rock Y=scissors Z=paper      6  worst\n"));
+        assert!(report.contains("    1  rock      paper        8  scissors     3\n"));
+    }
+}
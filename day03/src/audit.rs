@@ -0,0 +1,269 @@
+use std::str::FromStr;
+
+use aoc_common::{input_lines, AocError, InputLine};
+
+use crate::{compartments, intersect, Day03, ItemSet, DAY};
+
+// which rucksacks the audit lists
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Show {
+    #[default]
+    All,
+    Warnings,
+}
+
+impl FromStr for Show {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => Ok(Show::All),
+            "warnings" => Ok(Show::Warnings),
+            _ => Err(format!("unknown audit {s:?}")),
+        }
+    }
+}
+
+// one rucksack as both parts see it. problems that would stop a part are
+// kept as warnings, so the whole input is always audited
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Rucksack {
+    pub line: usize,
+    pub items: usize,
+    pub misplaced: Option<ItemSet>,
+    pub group: usize,
+    pub badge: Option<ItemSet>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Audit {
+    pub rucksacks: Vec<Rucksack>,
+    pub misplaced_priority: u32,
+    pub badge_priority: u32,
+    pub warnings: usize,
+}
+
+impl Day03 {
+    pub fn audit(&self, contents: &str) -> Result<Audit, AocError> {
+        if self.compartments == 0 || self.group == 0 {
+            return Err(AocError::unsolvable(
+                DAY,
+                "audits need at least 1 compartment and 1 elf per group",
+            ));
+        }
+
+        let lines: Vec<InputLine> = input_lines(DAY, contents).collect();
+        let mut rucksacks = Vec::new();
+        let mut badge_priority = 0;
+
+        for (g, group) in lines.chunks(self.group).enumerate() {
+            let sets: Vec<Result<ItemSet, AocError>> = group
+                .iter()
+                .map(|line| ItemSet::parse(line, line.text))
+                .collect();
+
+            let complete = group.len() == self.group;
+            let unparsed = group.iter().zip(&sets).find(|(_, set)| set.is_err());
+            let badge = match sets.iter().cloned().collect::<Result<Vec<_>, _>>() {
+                Ok(sets) if complete => Some(intersect(sets)),
+                _ => None,
+            };
+            let group_warning = match badge.map(|badge| badge.len()) {
+                _ if !complete => Some(format!(
+                    "group has {} of {} rucksacks",
+                    group.len(),
+                    self.group
+                )),
+                None => unparsed.map(|(line, _)| {
+                    format!("badge unavailable: line {} failed to parse", line.line)
+                }),
+                Some(0) => Some("group shares no item".to_string()),
+                Some(n) if n > 1 => Some(format!("group shares {n} items")),
+                _ => None,
+            };
+            badge_priority += badge.map_or(0, |badge| badge.priority_sum());
+
+            for (line, set) in group.iter().zip(sets) {
+                let mut rucksack = self.rucksack(line, set);
+                rucksack.group = g + 1;
+                rucksack.badge = badge;
+                rucksack.warnings.extend(group_warning.clone());
+                rucksacks.push(rucksack);
+            }
+        }
+
+        Ok(Audit {
+            misplaced_priority: rucksacks
+                .iter()
+                .filter_map(|r| r.misplaced)
+                .map(|m| m.priority_sum())
+                .sum(),
+            badge_priority,
+            warnings: rucksacks.iter().map(|r| r.warnings.len()).sum(),
+            rucksacks,
+        })
+    }
+
+    fn rucksack(&self, line: &InputLine, set: Result<ItemSet, AocError>) -> Rucksack {
+        let mut rucksack = Rucksack {
+            line: line.line,
+            items: line.text.chars().count(),
+            misplaced: None,
+            group: 0,
+            badge: None,
+            warnings: Vec::new(),
+        };
+
+        let misplaced = set.and_then(|_| {
+            compartments(line, self.compartments)?
+                .into_iter()
+                .map(|part| ItemSet::parse(line, part))
+                .collect::<Result<Vec<_>, _>>()
+        });
+        match misplaced.map(intersect) {
+            Ok(misplaced) => {
+                match misplaced.len() {
+                    0 => rucksack
+                        .warnings
+                        .push("no item in every compartment".into()),
+                    1 => {}
+                    n => rucksack
+                        .warnings
+                        .push(format!("{n} items in every compartment")),
+                }
+                rucksack.misplaced = Some(misplaced);
+            }
+            Err(err) => rucksack.warnings.push(warning(err)),
+        }
+        rucksack
+    }
+}
+
+// the error without the day and line, which the table already shows
+fn warning(err: AocError) -> String {
+    match err {
+        AocError::Parse {
+            column,
+            text,
+            reason,
+            ..
+        } => format!("{reason} at column {column} (found {text:?})"),
+        err => err.to_string(),
+    }
+}
+
+impl Audit {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("audits always serialize")
+    }
+
+    pub fn report(&self, show: Show) -> String {
+        let mut report = String::from(" line  items  misplaced  group  badge  warnings\n");
+        for r in &self.rucksacks {
+            if show == Show::Warnings && r.warnings.is_empty() {
+                continue;
+            }
+            let set = |set: Option<ItemSet>| set.map_or("-".to_string(), |s| s.to_string());
+            let row = format!(
+                "{:>5}  {:>5}  {:<9}  {:>5}  {:<5}  {}",
+                r.line,
+                r.items,
+                set(r.misplaced),
+                r.group,
+                set(r.badge),
+                r.warnings.join("; ")
+            );
+            report += row.trim_end();
+            report += "\n";
+        }
+
+        report += &format!("\nmisplaced priority {}\n", self.misplaced_priority);
+        report += &format!("badge priority     {}\n", self.badge_priority);
+        report += &format!("warnings           {}\n", self.warnings);
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw";
+
+    fn audit(input: &str) -> Audit {
+        Day03::default()
+            .audit(input)
+            .expect("test input should be audited")
+    }
+
+    #[test]
+    fn clean_input() {
+        let audit = audit(EXAMPLE);
+
+        assert_eq!(
+            (157, 70, 0),
+            (
+                audit.misplaced_priority,
+                audit.badge_priority,
+                audit.warnings
+            )
+        );
+        assert_eq!(
+            Rucksack {
+                line: 4,
+                items: 30,
+                misplaced: Some(ItemSet::parse(&InputLine::new(3, 4, "v"), "v").unwrap()),
+                group: 2,
+                badge: Some(ItemSet::parse(&InputLine::new(3, 4, "Z"), "Z").unwrap()),
+                warnings: Vec::new(),
+            },
+            audit.rucksacks[3]
+        );
+    }
+
+    #[test]
+    fn warnings() {
+        let audit = audit("abcab\nab1ba\nxyxy\nqq");
+        let warnings: Vec<String> = audit
+            .rucksacks
+            .iter()
+            .map(|r| r.warnings.join("; "))
+            .collect();
+
+        assert_eq!(
+            vec![
+                "5 items do not split into 2 compartments at column 1 (found \"abcab\"); \
+                 badge unavailable: line 2 failed to parse",
+                "item must be a letter at column 3 (found \"1\"); \
+                 badge unavailable: line 2 failed to parse",
+                "2 items in every compartment; badge unavailable: line 2 failed to parse",
+                "group has 1 of 3 rucksacks",
+            ],
+            warnings
+        );
+        assert_eq!(None, audit.rucksacks[0].badge);
+        assert_eq!(7, audit.warnings);
+    }
+
+    #[test]
+    fn report() {
+        let audit = audit(EXAMPLE);
+        let report = audit.report(Show::All);
+
+        assert!(report.starts_with(" line  items  misplaced  group  badge  warnings\n"));
+        assert!(report.contains("    1     24  p              1  r\n"));
+        assert!(report
+            .ends_with("misplaced priority 157\nbadge priority     70\nwarnings           0\n"));
+        assert!(!audit.report(Show::Warnings).contains("    1 "));
+    }
+
+    #[test]
+    fn json() {
+        let json = serde_json::to_value(audit("abab\nqq")).unwrap();
+
+        assert_eq!("ab", json["rucksacks"][0]["misplaced"]);
+        assert_eq!(serde_json::Value::Null, json["rucksacks"][0]["badge"]);
+        assert_eq!(3, json["warnings"]);
+    }
+}
@@ -0,0 +1,176 @@
+use std::fmt;
+use std::ops::{BitAnd, BitOr};
+
+use aoc_common::{AocError, InputLine};
+use serde::ser::{Serialize, Serializer};
+
+// a set of rucksack items as one bit per priority: a-z are bits 1 to 26 and
+// A-Z bits 27 to 52, so building, combining and scoring sets never allocates
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub const EMPTY: ItemSet = ItemSet(0);
+
+    // a-z are 1 to 26 and A-Z are 27 to 52. anything else has no priority
+    pub fn priority(item: char) -> Option<u32> {
+        match item {
+            'a'..='z' => Some(item as u32 - 'a' as u32 + 1),
+            'A'..='Z' => Some(item as u32 - 'A' as u32 + 27),
+            _ => None,
+        }
+    }
+
+    pub fn item(priority: u32) -> Option<char> {
+        match priority {
+            1..=26 => char::from_u32('a' as u32 + priority - 1),
+            27..=52 => char::from_u32('A' as u32 + priority - 27),
+            _ => None,
+        }
+    }
+
+    // the items in token, which must be part of line so a bad item can be
+    // pointed at
+    pub fn parse(line: &InputLine, token: &str) -> Result<ItemSet, AocError> {
+        let mut set = ItemSet::EMPTY;
+        for (i, c) in token.char_indices() {
+            if !set.insert(c) {
+                let item = &token[i..i + c.len_utf8()];
+                return Err(line.error(item, "item must be a letter"));
+            }
+        }
+        Ok(set)
+    }
+
+    // false, leaving the set alone, when item is not a letter
+    pub fn insert(&mut self, item: char) -> bool {
+        match ItemSet::priority(item) {
+            Some(priority) => {
+                self.0 |= 1 << priority;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn contains(&self, item: char) -> bool {
+        ItemSet::priority(item).is_some_and(|priority| self.0 & (1 << priority) != 0)
+    }
+
+    pub fn union(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 | other.0)
+    }
+
+    pub fn intersection(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    // the priorities of every item, added up
+    pub fn priority_sum(&self) -> u32 {
+        self.priorities().sum()
+    }
+
+    pub fn priorities(&self) -> impl Iterator<Item = u32> {
+        let bits = self.0;
+        (1..=52).filter(move |priority| bits & (1 << priority) != 0)
+    }
+
+    // the items in priority order
+    pub fn iter(&self) -> impl Iterator<Item = char> {
+        self.priorities().filter_map(ItemSet::item)
+    }
+}
+
+impl BitAnd for ItemSet {
+    type Output = ItemSet;
+
+    fn bitand(self, other: ItemSet) -> ItemSet {
+        self.intersection(other)
+    }
+}
+
+impl BitOr for ItemSet {
+    type Output = ItemSet;
+
+    fn bitor(self, other: ItemSet) -> ItemSet {
+        self.union(other)
+    }
+}
+
+impl fmt::Display for ItemSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(&self.iter().collect::<String>())
+    }
+}
+
+// sets read as their items, "pL" rather than a bit pattern
+impl Serialize for ItemSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(items: &str) -> ItemSet {
+        let line = InputLine::new(3, 1, items);
+        ItemSet::parse(&line, items).expect("test items should parse")
+    }
+
+    #[test]
+    fn priorities() {
+        assert_eq!(Some(1), ItemSet::priority('a'));
+        assert_eq!(Some(26), ItemSet::priority('z'));
+        assert_eq!(Some(27), ItemSet::priority('A'));
+        assert_eq!(Some(52), ItemSet::priority('Z'));
+        assert_eq!(None, ItemSet::priority('1'));
+        assert_eq!(None, ItemSet::priority('é'));
+        for priority in 1..=52 {
+            let item = ItemSet::item(priority).unwrap();
+            assert_eq!(Some(priority), ItemSet::priority(item));
+        }
+        assert_eq!(None, ItemSet::item(0));
+        assert_eq!(None, ItemSet::item(53));
+    }
+
+    #[test]
+    fn set_operations() {
+        let (a, b) = (set("vJrwpWtwJgWrhcsFMMfFFhFp"), set("hcsFMMfFFhFp"));
+
+        assert_eq!("cfhpsFM", (a & b).to_string());
+        assert_eq!(a, a | b);
+        assert_eq!(14, a.len());
+        assert!(a.contains('J') && !a.contains('j'));
+        assert!((set("ab") & set("cd")).is_empty());
+        assert_eq!(1 + 27 + 52, set("aAZZ").priority_sum());
+    }
+
+    #[test]
+    fn rejects_non_letters() {
+        let line = InputLine::new(3, 7, "abc1é");
+
+        assert_eq!(
+            "day 03, line 7, column 4: item must be a letter (found \"1\")",
+            ItemSet::parse(&line, line.text).unwrap_err().to_string()
+        );
+        assert_eq!(
+            "day 03, line 7, column 5: item must be a letter (found \"é\")",
+            ItemSet::parse(&line, &line.text[4..])
+                .unwrap_err()
+                .to_string()
+        );
+        let mut set = ItemSet::EMPTY;
+        assert!(!set.insert('-'));
+        assert!(set.is_empty());
+    }
+}
@@ -1,102 +1,98 @@
-use std::fs;
 use std::error::Error;
-use std::collections::HashSet;
 
+pub use aoc_common::Config;
+use aoc_common::{Answer, AocError, Format, Solution};
 
-pub struct Config {
-    file_path: String,
-    problem_number: i32,
-}
+mod audit;
+mod items;
+mod shared;
+pub use audit::{Audit, Rucksack, Show};
+pub use items::ItemSet;
+pub use shared::{compartment_items, compartments, group_items, intersect, Shared};
 
-impl Config {
-    pub fn build(args: &[String]) -> Result<Config, &'static str> {
-        if args.len() < 3 {
-            return Err("must provide input file_path and which problem to solve")
-        }
-        let file_path = args[1].clone();
-        let problem_number = args[2].clone().parse::<i32>().unwrap_or_default();
-
-        Ok(Config { file_path, problem_number })
-    }
-}
+const DAY: usize = 3;
 
 pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(config.file_path)?;
-    let result = solve(&contents, config.problem_number)?;
-    println!("result -> {result}");
-    Ok(())
-}
-
-fn solve(contents: &str, problem_number: i32) -> Result<i32, Box<dyn Error>> {
-
-    if problem_number == 1 {
-        return duplicate_items(contents)
-    } else {
-        return group_badge(contents)
+    let defaults = Day03::default();
+    let day = Day03 {
+        compartments: config
+            .option("--compartments")?
+            .unwrap_or(defaults.compartments),
+        group: config.option("--group")?.unwrap_or(defaults.group),
+    };
+
+    if let Some(show) = config.option("--audit")? {
+        return aoc_common::report::<Day03>(DAY, &config, |contents| {
+            let audit = day.audit(contents)?;
+            Ok(match config.format {
+                Format::Text => audit.report(show),
+                Format::Json => audit.to_json() + "\n",
+            })
+        });
     }
 
+    aoc_common::run(DAY, &day, config)
 }
 
-fn group_badge(contents: &str) -> Result<i32, Box<dyn Error>> {
-    let mut result = 0;
-    let mut lines = contents.lines().peekable();
-
-    while lines.peek().is_some() == true {
-        let c1 = lines.next().unwrap();
-        let c2 = lines.next().unwrap();
-        let c3 = lines.next().unwrap();
-
-        let mut compartment_one_chars = HashSet::new();
-        for c in c1.chars() {
-            compartment_one_chars.insert(c);
-        }
-
-        let mut compartment_two_chars = HashSet::new();
-        for c in c2.chars() {
-            compartment_two_chars.insert(c);
-        }
+pub struct Day03 {
+    pub compartments: usize,
+    pub group: usize,
+}
 
-        for c in c3.chars() {
-            if compartment_one_chars.contains(&c) && compartment_two_chars.contains(&c) {
-                result += calculate_priority(c);
-                break;
-            }
+impl Default for Day03 {
+    fn default() -> Self {
+        Day03 {
+            compartments: 2,
+            group: 3,
         }
     }
-
-    Ok(result)
 }
 
-fn duplicate_items(contents: &str) -> Result<i32, Box<dyn Error>> {
-    let mut result = 0;
-
-    for line in contents.lines() {
-        let mut compartment_one_chars = HashSet::new();
-        let compartment_one = &line[0..line.len()/2];
-        let compartment_two = &line[line.len()/2..line.len()];
+// each part's priority sum, folded over the lines as they are read. the
+// parts keep their own errors so a bad group does not fail part 1
+#[derive(Debug, Clone, PartialEq)]
+pub struct Priorities {
+    pub compartments: Result<u64, AocError>,
+    pub groups: Result<u64, AocError>,
+}
 
-        for c in compartment_one.chars() {
-            compartment_one_chars.insert(c);
-        }
+impl Solution for Day03 {
+    type Input = Priorities;
+
+    const OPTIONS: &'static [(&'static str, &'static str)] = &[
+        (
+            "--compartments K",
+            "how many compartments part 1 splits rucksacks into (default 2)",
+        ),
+        (
+            "--group G",
+            "how many elves part 2 puts in a group (default 3)",
+        ),
+        (
+            "--audit SHOW",
+            "table every rucksack instead, all or just warnings",
+        ),
+    ];
+
+    fn parse(&self, contents: &str) -> Result<Self::Input, AocError> {
+        Ok(Priorities {
+            compartments: priority_sum(compartment_items(contents, self.compartments)),
+            groups: priority_sum(group_items(contents, self.group)),
+        })
+    }
 
-        for c in compartment_two.chars() {
-            if compartment_one_chars.contains(&c) {
-                result += calculate_priority(c);
-                break;
-            }
-        }
+    fn part1(&self, input: &Self::Input) -> Result<Answer, AocError> {
+        input.compartments.clone().map(Answer::from)
     }
 
-    Ok(result)
+    fn part2(&self, input: &Self::Input) -> Result<Answer, AocError> {
+        input.groups.clone().map(Answer::from)
+    }
 }
 
-fn calculate_priority(c: char) -> i32 {
-    let c_num = c as i32;
-    if c_num > 96 {
-        return c_num - 96
-    } else {
-        return c_num - 65 + 27
-    }
+// every shared item counts, though puzzle inputs only ever share one
+fn priority_sum(shared: impl Iterator<Item = Result<Shared, AocError>>) -> Result<u64, AocError> {
+    shared.map(|shared| shared.map(|s| s.priority())).sum()
 }
 
 #[cfg(test)]
@@ -108,12 +104,12 @@ mod tests {
         let input = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw";
 
         let p = 1;
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day03::default().solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return -1
+            Answer::Int(-1)
         });
 
-        assert_eq!(157, result);
+        assert_eq!(Answer::Int(157), result);
     }
 
     #[test]
@@ -121,11 +117,28 @@ mod tests {
         let input = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw";
 
         let p = 2;
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day03::default().solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return -1
+            Answer::Int(-1)
         });
 
-        assert_eq!(70, result);
+        assert_eq!(Answer::Int(70), result);
+    }
+
+    #[test]
+    fn incomplete_group() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn";
+
+        let err = Day03::default().solve(input, 2).unwrap_err();
+
+        assert_eq!(
+            "day 03, line 4, column 1: group of elves needs 3 rucksacks (found \"wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\")",
+            err.to_string()
+        );
+        // the short group only fails part 2
+        assert_eq!(
+            Answer::Int(16 + 38 + 42 + 22),
+            Day03::default().solve(input, 1).unwrap()
+        );
     }
 }
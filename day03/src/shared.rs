@@ -0,0 +1,201 @@
+use std::iter;
+
+use aoc_common::{input_lines, AocError, InputLine};
+
+use crate::{ItemSet, DAY};
+
+// the items common to a run of item lists, found on line (the first line,
+// for a group of rucksacks)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shared {
+    pub line: usize,
+    pub items: ItemSet,
+}
+
+impl Shared {
+    pub fn priority(&self) -> u64 {
+        u64::from(self.items.priority_sum())
+    }
+}
+
+// every item in all of sets
+pub fn intersect(sets: impl IntoIterator<Item = ItemSet>) -> ItemSet {
+    let mut sets = sets.into_iter();
+    let first = sets.next().unwrap_or(ItemSet::EMPTY);
+    sets.fold(first, |shared, set| shared & set)
+}
+
+// the line split into k equal compartments
+pub fn compartments<'a>(line: &InputLine<'a>, k: usize) -> Result<Vec<&'a str>, AocError> {
+    if k == 0 {
+        return Err(AocError::unsolvable(
+            DAY,
+            "rucksacks need at least 1 compartment",
+        ));
+    }
+    let text = line.text;
+    if !text.len().is_multiple_of(k) {
+        let reason = format!("{} items do not split into {k} compartments", text.len());
+        return Err(line.error(text, reason));
+    }
+
+    let size = text.len() / k;
+    Ok((0..k).map(|i| &text[i * size..(i + 1) * size]).collect())
+}
+
+// the items shared by every compartment of each rucksack, a line at a time
+pub fn compartment_items(
+    contents: &str,
+    k: usize,
+) -> impl Iterator<Item = Result<Shared, AocError>> + '_ {
+    input_lines(DAY, contents).map(move |line| {
+        // the whole line first, so bad items are reported before a bad
+        // length
+        ItemSet::parse(&line, line.text)?;
+        let sets = compartments(&line, k)?
+            .into_iter()
+            .map(|part| ItemSet::parse(&line, part))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Shared {
+            line: line.line,
+            items: intersect(sets),
+        })
+    })
+}
+
+// the items shared by every rucksack in each group of size lines, a group at
+// a time. a group left short at the end of the input is an error naming its
+// last line, and nothing follows an error
+pub fn group_items(
+    contents: &str,
+    size: usize,
+) -> impl Iterator<Item = Result<Shared, AocError>> + '_ {
+    let mut lines = input_lines(DAY, contents);
+    let mut failed = false;
+    iter::from_fn(move || {
+        if failed {
+            return None;
+        }
+        let group = next_group(&mut lines, size);
+        failed = matches!(group, Some(Err(_)));
+        group
+    })
+}
+
+// None once the lines run out between groups
+fn next_group<'a>(
+    lines: &mut impl Iterator<Item = InputLine<'a>>,
+    size: usize,
+) -> Option<Result<Shared, AocError>> {
+    if size == 0 {
+        return Some(Err(AocError::unsolvable(DAY, "groups need at least 1 elf")));
+    }
+    let first = lines.next()?;
+    Some(fill_group(first, lines, size))
+}
+
+// the group that starts at first, taking the rest of its size from lines
+fn fill_group<'a>(
+    first: InputLine<'a>,
+    lines: &mut impl Iterator<Item = InputLine<'a>>,
+    size: usize,
+) -> Result<Shared, AocError> {
+    let (mut shared, mut last) = (ItemSet::parse(&first, first.text)?, first);
+    for _ in 1..size {
+        let Some(line) = lines.next() else {
+            let reason = format!("group of elves needs {size} rucksacks");
+            return Err(last.error(last.text, reason));
+        };
+        shared = shared & ItemSet::parse(&line, line.text)?;
+        last = line;
+    }
+
+    Ok(Shared {
+        line: first.line,
+        items: shared,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw";
+
+    fn items(shared: impl Iterator<Item = Result<Shared, AocError>>) -> Vec<String> {
+        shared
+            .map(|s| s.map(|s| s.items.to_string()))
+            .collect::<Result<_, _>>()
+            .expect("test input should have shared items")
+    }
+
+    fn error(mut shared: impl Iterator<Item = Result<Shared, AocError>>) -> String {
+        match shared.find_map(Result::err) {
+            Some(err) => err.to_string(),
+            None => "no error".to_string(),
+        }
+    }
+
+    fn set(items: &str) -> ItemSet {
+        items.chars().fold(ItemSet::EMPTY, |mut set, c| {
+            set.insert(c);
+            set
+        })
+    }
+
+    #[test]
+    fn all_shared_items() {
+        let shared = intersect([set("Bcab"), set("cBxb"), set("bBcc")]);
+
+        assert_eq!("bcB", shared.to_string());
+        assert!(intersect([set("ab"), set("cd")]).is_empty());
+        assert!(intersect([]).is_empty());
+    }
+
+    #[test]
+    fn k_compartments() {
+        assert_eq!(
+            vec!["p", "L", "P", "v", "t", "s"],
+            items(compartment_items(EXAMPLE, 2))
+        );
+        assert_eq!(vec!["ab"], items(compartment_items("abbaab", 3)));
+        assert_eq!(vec!["abcd"], items(compartment_items("abcbad", 1)));
+    }
+
+    #[test]
+    fn uneven_compartments() {
+        assert_eq!(
+            "day 03, line 2, column 1: 5 items do not split into 2 compartments (found \"abcde\")",
+            error(compartment_items("abcd\nabcde", 2))
+        );
+        assert_eq!(
+            "day 03: rucksacks need at least 1 compartment",
+            error(compartment_items("ab", 0))
+        );
+    }
+
+    #[test]
+    fn groups_of_any_size() {
+        assert_eq!(vec!["r", "Z"], items(group_items(EXAMPLE, 3)));
+        assert_eq!(
+            vec!["frsFM", "qvwBT", "GJZ"],
+            items(group_items(EXAMPLE, 2))
+        );
+        assert_eq!(6, items(group_items(EXAMPLE, 1)).len());
+    }
+
+    #[test]
+    fn incomplete_trailing_group() {
+        assert_eq!(
+            "day 03, line 6, column 1: group of elves needs 4 rucksacks (found \"CrZsJsPPZsGzwwsLwLmpwMDw\")",
+            error(group_items(EXAMPLE, 4))
+        );
+        assert_eq!(
+            "day 03: groups need at least 1 elf",
+            error(group_items(EXAMPLE, 0))
+        );
+        // the first group is whole, and nothing comes after the short one
+        assert_eq!(2, group_items(EXAMPLE, 4).count());
+        assert_eq!(1, group_items(EXAMPLE, 0).count());
+    }
+}
@@ -0,0 +1,258 @@
+use std::str::FromStr;
+
+use aoc_common::AocError;
+
+use crate::{Interval, Section, DAY};
+
+// how much of the coverage the report lists
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Show {
+    #[default]
+    Summary,
+    All,
+}
+
+impl FromStr for Show {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "summary" => Ok(Show::Summary),
+            "all" => Ok(Show::All),
+            _ => Err(format!("unknown coverage {s:?}")),
+        }
+    }
+}
+
+// every assignment in a schedule taken together. runs is the normalised set:
+// sorted, with no two runs overlapping or touching, and gaps are the
+// sections between them that nobody cleans
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Coverage {
+    pub assignments: usize,
+    pub runs: Vec<Interval>,
+    pub covered: usize,
+    pub shared: usize,
+    pub gaps: Vec<Interval>,
+    pub uncovered: usize,
+    pub max_depth: usize,
+    pub deepest: Vec<Interval>,
+}
+
+impl Coverage {
+    pub fn new(sections: &[Section]) -> Result<Coverage, AocError> {
+        if sections.is_empty() {
+            return Err(AocError::unsolvable(
+                DAY,
+                "there are no assignments to report on",
+            ));
+        }
+
+        let runs = normalise(sections);
+        let gaps: Vec<Interval> = runs
+            .windows(2)
+            .filter_map(|pair| Interval::new(pair[0].close() + 1, pair[1].open() - 1))
+            .collect();
+
+        // a sweep over where assignments start and stop. an assignment stops
+        // just past its close, which is why positions are widened to i64
+        let mut events: Vec<(i64, i64)> = sections
+            .iter()
+            .flat_map(|s| [(s.open() as i64, 1), (s.close() as i64 + 1, -1)])
+            .collect();
+        events.sort_unstable();
+
+        let (mut depth, mut shared, mut max_depth) = (0, 0, 0);
+        let mut deepest = Vec::new();
+        for (i, &(at, change)) in events.iter().enumerate() {
+            depth += change;
+            // every section from here up to the next event is at this depth
+            let Some(&(next, _)) = events.get(i + 1) else {
+                break;
+            };
+            // only a close at i32::MAX puts events past the last section, and
+            // nothing starts after it
+            let (Ok(open), Ok(close)) = (i32::try_from(at), i32::try_from(next - 1)) else {
+                break;
+            };
+            let Some(run) = Interval::new(open, close) else {
+                continue;
+            };
+
+            if depth > 1 {
+                shared += run.len();
+            }
+            if depth > max_depth {
+                max_depth = depth;
+                deepest.clear();
+            }
+            if depth == max_depth {
+                push_merged(&mut deepest, run);
+            }
+        }
+
+        Ok(Coverage {
+            assignments: sections.len(),
+            covered: runs.iter().map(Interval::len).sum(),
+            uncovered: gaps.iter().map(Interval::len).sum(),
+            runs,
+            shared,
+            gaps,
+            max_depth: max_depth as usize,
+            deepest,
+        })
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("coverage always serializes")
+    }
+
+    pub fn report(&self, show: Show) -> String {
+        let list = |runs: &[Interval]| match runs.len() {
+            0 => "-".to_string(),
+            _ => runs
+                .iter()
+                .map(Interval::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+
+        let mut report = format!("assignments  {}\n", self.assignments);
+        report += &format!(
+            "covered      {} sections in {} runs\n",
+            self.covered,
+            self.runs.len()
+        );
+        report += &format!("shared       {} sections\n", self.shared);
+        report += &format!(
+            "uncovered    {} sections in {} gaps\n",
+            self.uncovered,
+            self.gaps.len()
+        );
+        report += &format!(
+            "max depth    {} at {}\n",
+            self.max_depth,
+            list(&self.deepest)
+        );
+
+        if show == Show::All {
+            report += &format!("\nruns  {}\n", list(&self.runs));
+            report += &format!("gaps  {}\n", list(&self.gaps));
+        }
+        report
+    }
+}
+
+// sections sorted and merged wherever they overlap or touch
+pub fn normalise(sections: &[Section]) -> Vec<Interval> {
+    let mut sorted = sections.to_vec();
+    sorted.sort_unstable();
+
+    let mut runs = Vec::new();
+    for section in sorted {
+        push_merged(&mut runs, section);
+    }
+    runs
+}
+
+// runs must be sorted and run must not start before the last of them
+fn push_merged(runs: &mut Vec<Interval>, run: Interval) {
+    match runs.last_mut() {
+        Some(last) => match last.union(&run) {
+            Some(merged) => *last = merged,
+            None => runs.push(run),
+        },
+        None => runs.push(run),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Solution;
+
+    use crate::Day04;
+
+    const EXAMPLE: &str = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n3-14,14-80";
+
+    fn coverage(input: &str) -> Coverage {
+        Day04
+            .parse(input)
+            .and_then(|pairs| Coverage::new(pairs.concat().as_slice()))
+            .expect("test input should have coverage")
+    }
+
+    fn list(runs: &[Interval]) -> Vec<String> {
+        runs.iter().map(Interval::to_string).collect()
+    }
+
+    #[test]
+    fn example() {
+        let coverage = coverage(EXAMPLE);
+
+        assert_eq!(vec!["2-80"], list(&coverage.runs));
+        assert_eq!(
+            (14, 79, 9, 0, 9),
+            (
+                coverage.assignments,
+                coverage.covered,
+                coverage.shared,
+                coverage.uncovered,
+                coverage.max_depth
+            )
+        );
+        assert_eq!(vec!["6-6"], list(&coverage.deepest));
+    }
+
+    #[test]
+    fn gaps() {
+        let coverage = coverage("2-4,6-8\n12-13,3-3\n9-9,14-15");
+
+        assert_eq!(vec!["2-4", "6-9", "12-15"], list(&coverage.runs));
+        assert_eq!(vec!["5-5", "10-11"], list(&coverage.gaps));
+        assert_eq!(
+            (11, 1, 3),
+            (coverage.covered, coverage.shared, coverage.uncovered)
+        );
+        assert_eq!(
+            (2, vec!["3-3".to_string()]),
+            (coverage.max_depth, list(&coverage.deepest))
+        );
+    }
+
+    #[test]
+    fn deepest_runs_merge() {
+        // 3-5 and 6-8 are both 2 deep, one assignment handing over to another
+        let coverage = coverage("1-5,3-8\n6-9,20-20");
+
+        assert_eq!(vec!["3-8"], list(&coverage.deepest));
+        assert_eq!(vec!["1-9", "20-20"], list(&coverage.runs));
+    }
+
+    #[test]
+    fn sections_up_to_i32_max() {
+        let coverage = coverage("1-2147483647,5-6\n2147483647-2147483647,3-3");
+
+        assert_eq!(vec!["1-2147483647"], list(&coverage.runs));
+        assert!(coverage.gaps.is_empty());
+        assert_eq!(
+            (i32::MAX as usize, 4, 0),
+            (coverage.covered, coverage.shared, coverage.uncovered)
+        );
+        assert_eq!(
+            vec!["3-3", "5-6", "2147483647-2147483647"],
+            list(&coverage.deepest)
+        );
+    }
+
+    #[test]
+    fn report() {
+        let report = coverage("2-4,6-8\n12-13,3-3").report(Show::All);
+
+        assert_eq!(
+            "assignments  4\ncovered      8 sections in 3 runs\nshared       1 sections\nuncovered    4 sections in 2 gaps\nmax depth    2 at 3-3\n\nruns  2-4, 6-8, 12-13\ngaps  5-5, 9-11\n",
+            report
+        );
+        assert!(Coverage::new(&[]).is_err());
+    }
+}
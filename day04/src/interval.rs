@@ -0,0 +1,133 @@
+use std::fmt;
+
+use aoc_common::{AocError, InputLine};
+use serde::ser::{Serialize, Serializer};
+
+// an inclusive run of section ids, open to close. open is never past close
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval {
+    open: i32,
+    close: i32,
+}
+
+impl Interval {
+    // None when close comes before open
+    pub fn new(open: i32, close: i32) -> Option<Interval> {
+        (open <= close).then_some(Interval { open, close })
+    }
+
+    // "start-end", where token is part of line so errors point into it
+    pub fn parse(line: &InputLine, token: &str) -> Result<Interval, AocError> {
+        let Some((open, close)) = token.split_once('-') else {
+            return Err(line.error(token, "section must look like start-end"));
+        };
+
+        Interval::new(line.parse_num(open)?, line.parse_num(close)?)
+            .ok_or_else(|| line.error(token, "section ends before it starts"))
+    }
+
+    pub fn open(&self) -> i32 {
+        self.open
+    }
+
+    pub fn close(&self) -> i32 {
+        self.close
+    }
+
+    // how many ids are in the interval, never 0
+    pub fn len(&self) -> usize {
+        (self.close as i64 - self.open as i64) as usize + 1
+    }
+
+    // always false, an interval holds at least its open id
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    // whether every id in other is also in self
+    pub fn contains(&self, other: &Interval) -> bool {
+        self.open <= other.open && other.close <= self.close
+    }
+
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        self.open <= other.close && other.open <= self.close
+    }
+
+    // the ids in both, if any
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        Interval::new(self.open.max(other.open), self.close.min(other.close))
+    }
+
+    // the ids in either, when they make a single interval: the two overlap,
+    // or one ends right before the other starts
+    pub fn union(&self, other: &Interval) -> Option<Interval> {
+        // widened so an interval closing at i32::MAX still touches the next id
+        let touches = self.close as i64 + 1 >= other.open as i64
+            && other.close as i64 + 1 >= self.open as i64;
+
+        touches.then(|| Interval {
+            open: self.open.min(other.open),
+            close: self.close.max(other.close),
+        })
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.open, self.close)
+    }
+}
+
+// intervals read as they are written, "2-4"
+impl Serialize for Interval {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interval(open: i32, close: i32) -> Interval {
+        Interval::new(open, close).unwrap_or_else(|| {
+            println!("failed to unwrap test result, {open}-{close} is backwards");
+            Interval { open: 0, close: 0 }
+        })
+    }
+
+    #[test]
+    fn algebra() {
+        let (a, b) = (interval(2, 6), interval(4, 8));
+
+        assert_eq!(5, a.len());
+        assert_eq!(1, interval(6, 6).len());
+        assert!(a.contains(&interval(3, 6)) && !a.contains(&b));
+        assert!(a.overlaps(&b) && b.overlaps(&a));
+        assert!(!interval(2, 3).overlaps(&interval(4, 5)));
+        assert_eq!(Some(interval(4, 6)), a.intersection(&b));
+        assert_eq!(None, interval(2, 3).intersection(&interval(4, 5)));
+        assert_eq!(Some(interval(2, 8)), a.union(&b));
+        assert_eq!(Some(interval(2, 5)), interval(2, 3).union(&interval(4, 5)));
+        assert_eq!(None, interval(2, 3).union(&interval(5, 6)));
+        assert_eq!(None, Interval::new(5, 4));
+
+        let widest = interval(1, i32::MAX);
+        assert_eq!(Some(widest), widest.union(&interval(5, 6)));
+        assert_eq!(Some(widest), interval(5, 6).union(&widest));
+        assert_eq!(i32::MAX as usize, widest.len());
+    }
+
+    #[test]
+    fn parse() {
+        let line = InputLine::new(4, 3, "2-4,8-6");
+        let (first, second) = line.text.split_once(',').unwrap();
+
+        assert_eq!(interval(2, 4), Interval::parse(&line, first).unwrap());
+        assert_eq!(
+            "day 04, line 3, column 5: section ends before it starts (found \"8-6\")",
+            Interval::parse(&line, second).unwrap_err().to_string()
+        );
+        assert_eq!("2-4", interval(2, 4).to_string());
+    }
+}
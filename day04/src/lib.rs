@@ -1,110 +1,74 @@
-use std::fs;
 use std::error::Error;
 
+pub use aoc_common::Config;
+use aoc_common::{input_lines, Answer, AocError, Format, InputLine, Solution};
 
-pub struct Config {
-    file_path: String,
-    problem_number: i32,
-}
+mod coverage;
+mod interval;
 
-struct Section {
-    open: i32,
-    close: i32
-}
+pub use coverage::{normalise, Coverage, Show};
+pub use interval::Interval;
 
-impl Config {
-    pub fn build(args: &[String]) -> Result<Config, &'static str> {
-        if args.len() < 3 {
-            return Err("must provide input file_path and which problem to solve")
-        }
-        let file_path = args[1].clone();
-        let problem_number = args[2].clone().parse::<i32>().unwrap_or_default();
+const DAY: usize = 4;
 
-        Ok(Config { file_path, problem_number })
-    }
-}
+// the run of sections one elf is assigned to clean
+pub type Section = Interval;
 
 pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(config.file_path)?;
-    let result = solve(&contents, config.problem_number)?;
-    println!("result -> {result}");
-    Ok(())
-}
-
-fn solve(contents: &str, problem_number: i32) -> Result<i32, Box<dyn Error>> {
-
-    if problem_number == 1 {
-        return check_containment(contents)
-    } else {
-        return check_overlap(contents)
-    }
-
-}
-
-fn get_sections(line: &str) -> Result<Vec<Section>, Box<dyn Error>> {
-    let parts: Vec<&str> = line.split(",").collect();
-
-    if parts.len() < 2 {
-        Err("does not have two sections of coverage {line}")?
+    if let Some(show) = config.option("--coverage")? {
+        return aoc_common::report::<Day04>(DAY, &config, |contents| {
+            let coverage = Coverage::new(&Day04.parse(contents)?.concat())?;
+            Ok(match config.format {
+                Format::Text => coverage.report(show),
+                Format::Json => coverage.to_json() + "\n",
+            })
+        });
     }
 
-    let first_sections: Vec<&str>  = parts[0].split("-").collect();
-    let second_sections: Vec<&str> = parts[1].split("-").collect();
-
-    let section_one = Section{open: first_sections[0].parse::<i32>().unwrap(), close: first_sections[1].parse::<i32>().unwrap()};
-    let section_two = Section{open: second_sections[0].parse::<i32>().unwrap(), close: second_sections[1].parse::<i32>().unwrap()};
-
-    Ok(vec![section_one, section_two])
+    aoc_common::run(DAY, &Day04, config)
 }
 
-fn check_overlap(contents: &str) -> Result<i32, Box<dyn Error>> {
-    let mut result = 0;
+#[derive(Default)]
+pub struct Day04;
 
-    for line in contents.lines() {
-        let sections = get_sections(line)?;
+impl Solution for Day04 {
+    type Input = Vec<[Section; 2]>;
 
-        if sections[0].open >= sections[1].open && sections[0].open <= sections[1].close {
-            result += 1
-        } else if sections[1].open >= sections[0].open && sections[1].open <= sections[0].close {
-            result += 1
-        } else if sections[0].close <= sections[1].close && sections[0].close >= sections[1].open {
-            result += 1
-        } else if sections[1].close <= sections[0].close && sections[1].close >= sections[0].open {
-            result += 1
-        }
+    const OPTIONS: &'static [(&'static str, &'static str)] = &[(
+        "--coverage SHOW",
+        "merge every assignment instead, a summary or all runs and gaps",
+    )];
 
+    fn parse(&self, contents: &str) -> Result<Self::Input, AocError> {
+        input_lines(DAY, contents).map(get_sections).collect()
     }
 
-    Ok(result)
-}
-
-fn check_containment(contents: &str) -> Result<i32, Box<dyn Error>> {
-    let mut result = 0;
-
-    for line in contents.lines() {
-        let parts: Vec<&str> = line.split(",").collect();
-
-        if parts.len() < 2 {
-            Err("does not have two sections of coverage {line}")?
-        }
-
-        let first_sections: Vec<&str>  = parts[0].split("-").collect();
-        let second_sections: Vec<&str> = parts[1].split("-").collect();
-
-        // check if sections are covered by the other sections 
-        if first_sections[0].parse::<i32>().unwrap() >= second_sections[0].parse::<i32>().unwrap() && 
-            first_sections[1].parse::<i32>().unwrap() <= second_sections[1].parse::<i32>().unwrap() { 
-            result += 1;
-        } else if second_sections[0].parse::<i32>().unwrap() >= first_sections[0].parse::<i32>().unwrap() && 
-            second_sections[1].parse::<i32>().unwrap() <= first_sections[1].parse::<i32>().unwrap() {
-            result += 1;
-        }
+    fn part1(&self, input: &Self::Input) -> Result<Answer, AocError> {
+        let contained = input
+            .iter()
+            .filter(|[a, b]| a.contains(b) || b.contains(a))
+            .count();
+        Ok((contained as u64).into())
     }
 
-    Ok(result)
+    fn part2(&self, input: &Self::Input) -> Result<Answer, AocError> {
+        let overlapping = input.iter().filter(|[a, b]| a.overlaps(b)).count();
+        Ok((overlapping as u64).into())
+    }
 }
 
-
+// a line is one pair of elves, "start-end,start-end"
+fn get_sections(line: InputLine) -> Result<[Section; 2], AocError> {
+    let mut parts = line.text.split(',');
+    let (Some(first), Some(second), None) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(line.error(line.text, "does not have two sections of coverage"));
+    };
+
+    Ok([
+        Section::parse(&line, first)?,
+        Section::parse(&line, second)?,
+    ])
+}
 
 #[cfg(test)]
 mod tests {
@@ -115,12 +79,12 @@ mod tests {
         let input = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n3-14,14-80";   
         let p = 1;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day04.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return -1
+            Answer::Int(-1)
         });
 
-        assert_eq!(2, result);
+        assert_eq!(Answer::Int(2), result);
     }
 
     #[test]
@@ -128,11 +92,33 @@ mod tests {
         let input = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n3-14,14-80";   
         let p = 2;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day04.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return -1
+            Answer::Int(-1)
         });
 
-        assert_eq!(5, result);
+        assert_eq!(Answer::Int(5), result);
+    }
+
+    #[test]
+    fn backwards_section() {
+        let err = Day04.solve("2-4,6-8\n4-2,1-1", 1).unwrap_err();
+
+        assert_eq!(
+            "day 04, line 2, column 1: section ends before it starts (found \"4-2\")",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn bad_section() {
+        let input = "2-4,6-8\n2-3,4-x";
+
+        let err = Day04.solve(input, 1).unwrap_err();
+
+        assert_eq!(
+            "day 04, line 2, column 7: expected a number (found \"x\")",
+            err.to_string()
+        );
     }
 }
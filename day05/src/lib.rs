@@ -1,97 +1,137 @@
-use std::fs;
 use std::error::Error;
 
+pub use aoc_common::Config;
+use aoc_common::{input_lines, Answer, AocError, InputLine, Solution};
 
-pub struct Config {
-    file_path: String,
-    problem_number: i32,
-}
+const DAY: usize = 5;
 
-impl Config {
-    pub fn build(args: &[String]) -> Result<Config, &'static str> {
-        if args.len() < 3 {
-            return Err("must provide input file_path and which problem to solve")
-        }
-        let file_path = args[1].clone();
-        let problem_number = args[2].clone().parse::<i32>().unwrap_or_default();
 
-        Ok(Config { file_path, problem_number })
-    }
+pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
+    aoc_common::run(DAY, &Day05, config)
 }
 
-pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(config.file_path)?;
-    let result = solve(&contents, config.problem_number)?;
-    println!("result -> {result}");
-    Ok(())
+#[derive(Default)]
+pub struct Day05;
+
+// a parsed "move N from S to D" line, with stacks zero-indexed
+#[derive(Debug, Clone, Copy)]
+pub struct Instruction {
+    line: usize,
+    count: usize,
+    source: usize,
+    dest: usize,
 }
 
-fn solve(contents: &str, problem_number: i32) -> Result<String, Box<dyn Error>> {
+impl Solution for Day05 {
+    type Input = (Vec<Vec<char>>, Vec<Instruction>);
 
-    let parts: Vec<&str> = contents.split("\n\n").collect();
+    fn parse(&self, contents: &str) -> Result<Self::Input, AocError> {
+        let lines: Vec<InputLine> = input_lines(DAY, contents).collect();
 
-    if parts.len() < 2 {
-        Err("did not provide starting stacks and instructions in proper format")?
-    }
+        let split = match lines.iter().position(|l| l.text.is_empty()) {
+            Some(split) if split > 0 => split,
+            _ => return Err(AocError::unsolvable(DAY, "did not provide starting stacks and instructions in proper format")),
+        };
 
-    let top: Vec<&str> = parts[0].split("\n").collect();
-    // might be the single worst line of code I've ever seen
-    let cnt = top[top.len()-1].chars().nth(top[top.len()-1].len()-2).unwrap().to_digit(10).unwrap();
+        // the last line of the drawing numbers the stacks
+        let numbers = lines[split-1];
+        let mut cnt = 0;
+        for token in numbers.text.split_whitespace() {
+            numbers.parse_num::<usize>(token)?;
+            cnt += 1;
+        }
+
+        let top: Vec<&str> = lines[0..split-1].iter().map(|l| l.text).collect();
+        let stacks: Vec<Vec<char>> = create_starting_stacks(top, cnt);
 
-    let stacks: Vec<Vec<char>> = create_starting_stacks(top[0..top.len()-1].to_vec(), cnt as usize);
-    let instructions = parts[1];
+        let mut instructions = Vec::new();
+        for line in &lines[split+1..] {
+            if !line.text.is_empty() {
+                instructions.push(parse_instruction(*line, cnt)?);
+            }
+        }
 
-    if problem_number == 1 {
-        return top_boxes_9000(stacks, instructions);
-    } else {
-        return top_boxes_9001(stacks, instructions);
+        Ok((stacks, instructions))
     }
 
+    fn part1(&self, input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(top_boxes_9000(input.0.clone(), &input.1)?.into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(top_boxes_9001(input.0.clone(), &input.1)?.into())
+    }
 }
 
-fn top_boxes_9001(mut stacks: Vec<Vec<char>>, instructions: &str) -> Result<String, Box<dyn Error>> {
-    for instr in instructions.lines() {
-        let (to_move, source, dest) = parse_instruction(instr);
+fn top_boxes_9001(mut stacks: Vec<Vec<char>>, instructions: &[Instruction]) -> Result<String, AocError> {
+    for instr in instructions {
+        check_move(&stacks, instr)?;
 
-        let top = stacks[source].len();
-        let bottom = top-to_move as usize;
-        let mut to_append: Vec<char> = stacks[source].splice(bottom..top, []).collect();
-        stacks[dest].append(&mut to_append);
+        let top = stacks[instr.source].len();
+        let bottom = top-instr.count;
+        let mut to_append: Vec<char> = stacks[instr.source].splice(bottom..top, []).collect();
+        stacks[instr.dest].append(&mut to_append);
     }
 
-    Ok(top_boxes_string(stacks))
+    top_boxes_string(stacks)
 }
 
 
-fn top_boxes_9000(mut stacks: Vec<Vec<char>>, instructions: &str) -> Result<String, Box<dyn Error>> {
-    for instr in instructions.lines() {
-        let (to_move, source, dest) = parse_instruction(instr);
+fn top_boxes_9000(mut stacks: Vec<Vec<char>>, instructions: &[Instruction]) -> Result<String, AocError> {
+    for instr in instructions {
+        check_move(&stacks, instr)?;
 
-        for _i in 0..to_move {
-            let c = stacks[source].pop().unwrap();
-            stacks[dest].push(c);
+        for _i in 0..instr.count {
+            if let Some(c) = stacks[instr.source].pop() {
+                stacks[instr.dest].push(c);
+            }
         }
     }
 
-    Ok(top_boxes_string(stacks))
+    top_boxes_string(stacks)
 }
 
-fn parse_instruction(instr: &str) -> (i32, usize, usize) {
-    let parts: Vec<&str> = instr.split("from").collect();
-    let to_move = parts[0].split(" ").nth(1).unwrap().parse::<i32>().unwrap();
-    let source = parts[1].split(" ").nth(1).unwrap().parse::<usize>().unwrap();
-    let dest = parts[1].split(" ").nth(3).unwrap().parse::<usize>().unwrap();
+fn check_move(stacks: &[Vec<char>], instr: &Instruction) -> Result<(), AocError> {
+    if stacks[instr.source].len() < instr.count {
+        return Err(AocError::unsolvable(DAY, format!(
+            "line {} moves {} crates but stack {} only holds {}",
+            instr.line, instr.count, instr.source+1, stacks[instr.source].len()
+        )));
+    }
 
-    return (to_move, source-1, dest-1)
+    Ok(())
 }
 
-fn top_boxes_string(stacks: Vec<Vec<char>>) -> String {
+fn parse_instruction(line: InputLine, cnt: usize) -> Result<Instruction, AocError> {
+    let parts: Vec<&str> = line.text.split_whitespace().collect();
+    if parts.len() != 6 || parts[0] != "move" || parts[2] != "from" || parts[4] != "to" {
+        return Err(line.error(line.text, "instruction must look like move N from S to D"));
+    }
+
+    let count = line.parse_num::<usize>(parts[1])?;
+    let stack = |token: &str| -> Result<usize, AocError> {
+        let num = line.parse_num::<usize>(token)?;
+        if num == 0 || num > cnt {
+            return Err(line.error(token, format!("stack must be between 1 and {cnt}")));
+        }
+        Ok(num-1)
+    };
+    let source = stack(parts[3])?;
+    let dest = stack(parts[5])?;
+
+    Ok(Instruction { line: line.line, count, source, dest })
+}
+
+fn top_boxes_string(stacks: Vec<Vec<char>>) -> Result<String, AocError> {
     let mut result = String::new();
-    for i in 0..stacks.len() {
-        result.push(stacks[i][stacks[i].len()-1])
+    for (i, stack) in stacks.iter().enumerate() {
+        match stack.last() {
+            Some(c) => result.push(*c),
+            None => return Err(AocError::unsolvable(DAY, format!("stack {} ends up empty", i+1))),
+        }
     }
 
-    return result
+    Ok(result)
 }
 
 
@@ -113,7 +153,7 @@ fn create_starting_stacks(top: Vec<&str>, cnt: usize) -> Vec<Vec<char>> {
 
     }
 
-    return stacks;
+    stacks
 }
 
 #[cfg(test)]
@@ -125,12 +165,12 @@ mod tests {
         let input = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\nmove 1 from 1 to 2";
         let p = 1;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day05.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return String::new()
+            Answer::Text(String::new())
         });
 
-        assert_eq!("CMZ", result);
+        assert_eq!(Answer::Text("CMZ".to_string()), result);
     }
 
     #[test]
@@ -138,11 +178,23 @@ mod tests {
         let input = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\nmove 1 from 1 to 2";
         let p = 2;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day05.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return String::new()
+            Answer::Text(String::new())
         });
 
-        assert_eq!("MCD", result);
+        assert_eq!(Answer::Text("MCD".to_string()), result);
+    }
+
+    #[test]
+    fn bad_instruction() {
+        let input = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 3 from 1 to 4";
+
+        let err = Day05.solve(input, 1).unwrap_err();
+
+        assert_eq!(
+            "day 05, line 7, column 18: stack must be between 1 and 3 (found \"4\")",
+            err.to_string()
+        );
     }
 }
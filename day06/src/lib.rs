@@ -1,56 +1,49 @@
-use std::fs;
 use std::collections::HashSet;
 use std::error::Error;
 
+pub use aoc_common::Config;
+use aoc_common::{Answer, AocError, Solution};
 
-pub struct Config {
-    file_path: String,
-    problem_number: i32,
-}
-
-impl Config {
-    pub fn build(args: &[String]) -> Result<Config, &'static str> {
-        if args.len() < 3 {
-            return Err("must provide input file_path and which problem to solve")
-        }
-        let file_path = args[1].clone();
-        let problem_number = args[2].clone().parse::<i32>().unwrap_or_default();
+const DAY: usize = 6;
 
-        Ok(Config { file_path, problem_number })
-    }
-}
 
 pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(config.file_path)?;
-    let result = solve(&contents, config.problem_number)?;
-    println!("result -> {result}");
-    Ok(())
+    aoc_common::run(DAY, &Day06, config)
 }
 
-fn solve(contents: &str, problem_number: i32) -> Result<i32, Box<dyn Error>> {
+#[derive(Default)]
+pub struct Day06;
 
-    if problem_number == 1 {
-        return packet_start_index(contents, 4);
-    } else {
-        return packet_start_index(contents, 14);
+impl Solution for Day06 {
+    type Input = String;
+
+    fn parse(&self, contents: &str) -> Result<Self::Input, AocError> {
+        Ok(contents.to_string())
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(packet_start_index(input, 4)?.into())
     }
 
+    fn part2(&self, input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(packet_start_index(input, 14)?.into())
+    }
 }
 
-fn packet_start_index(contents: &str, distinct_count: usize) -> Result<i32, Box<dyn Error>> {
+fn packet_start_index(contents: &str, distinct_count: usize) -> Result<i32, AocError> {
 
+    let chars: Vec<char> = contents.chars().collect();
     let mut c_set = HashSet::new();
     let mut left: usize = 0;
 
-    for (right, c) in contents.chars().enumerate() {
+    for (right, c) in chars.iter().copied().enumerate() {
 
         if right - left >= distinct_count {
             return Ok(right as i32)
         }
 
         while c_set.contains(&c) {
-            let l_char = &contents.chars().nth(left).unwrap();
-            c_set.remove(l_char);
+            c_set.remove(&chars[left]);
             left += 1;
         }
 
@@ -58,7 +51,7 @@ fn packet_start_index(contents: &str, distinct_count: usize) -> Result<i32, Box<
 
     }
 
-    Err("no packet start index found")?
+    Err(AocError::unsolvable(DAY, "no packet start index found"))
 }
 
 #[cfg(test)]
@@ -70,12 +63,12 @@ mod tests {
         let input = "bvwbjplbgvbhsrlpgdmjqwftvncz";
         let p = 1;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day06.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return 0
+            Answer::Int(0)
         });
 
-        assert_eq!(5, result);
+        assert_eq!(Answer::Int(5), result);
     }
 
 
@@ -84,12 +77,12 @@ mod tests {
         let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
         let p = 1;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day06.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return 0
+            Answer::Int(0)
         });
 
-        assert_eq!(7, result);
+        assert_eq!(Answer::Int(7), result);
     }
 
     #[test]
@@ -97,12 +90,12 @@ mod tests {
         let input = "nppdvjthqldpwncqszvftbrmjlhg";
         let p = 1;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day06.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return 0
+            Answer::Int(0)
         });
 
-        assert_eq!(6, result);
+        assert_eq!(Answer::Int(6), result);
     }
 
     #[test]
@@ -110,12 +103,12 @@ mod tests {
         let input = "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg";
         let p = 1;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day06.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return 0
+            Answer::Int(0)
         });
 
-        assert_eq!(10, result);
+        assert_eq!(Answer::Int(10), result);
     }
 
     #[test]
@@ -123,12 +116,12 @@ mod tests {
         let input = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
         let p = 1;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day06.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return 0
+            Answer::Int(0)
         });
 
-        assert_eq!(11, result);
+        assert_eq!(Answer::Int(11), result);
     }
 
     #[test]
@@ -136,12 +129,12 @@ mod tests {
         let input = "bvwbjplbgvbhsrlpgdmjqwftvncz";
         let p = 2;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day06.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return 0
+            Answer::Int(0)
         });
 
-        assert_eq!(23, result);
+        assert_eq!(Answer::Int(23), result);
     }
 
 
@@ -150,12 +143,12 @@ mod tests {
         let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
         let p = 2;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day06.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return 0
+            Answer::Int(0)
         });
 
-        assert_eq!(19, result);
+        assert_eq!(Answer::Int(19), result);
     }
 
     #[test]
@@ -163,12 +156,12 @@ mod tests {
         let input = "nppdvjthqldpwncqszvftbrmjlhg";
         let p = 2;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day06.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return 0
+            Answer::Int(0)
         });
 
-        assert_eq!(23, result);
+        assert_eq!(Answer::Int(23), result);
     }
 
     #[test]
@@ -176,12 +169,12 @@ mod tests {
         let input = "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg";
         let p = 2;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day06.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return 0
+            Answer::Int(0)
         });
 
-        assert_eq!(29, result);
+        assert_eq!(Answer::Int(29), result);
     }
 
     #[test]
@@ -189,11 +182,11 @@ mod tests {
         let input = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
         let p = 2;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day06.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return 0
+            Answer::Int(0)
         });
 
-        assert_eq!(26, result);
+        assert_eq!(Answer::Int(26), result);
     }
 }
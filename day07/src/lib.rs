@@ -1,53 +1,43 @@
-use std::fs;
 use std::error::Error;
 use std::collections::HashMap;
 
-pub struct Config {
-    file_path: String,
-    problem_number: i32,
-}
-
-impl Config {
-    pub fn build(args: &[String]) -> Result<Config, &'static str> {
-        if args.len() < 3 {
-            return Err("must provide input file_path and which problem to solve")
-        }
-        let file_path = args[1].clone();
-        let problem_number = args[2].clone().parse::<i32>().unwrap_or_default();
+pub use aoc_common::Config;
+use aoc_common::{input_lines, Answer, AocError, Solution};
 
-        Ok(Config { file_path, problem_number })
-    }
-}
+const DAY: usize = 7;
 
 pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(config.file_path)?;
-    let result = solve(&contents, config.problem_number)?;
-    println!("result -> {result}");
-    Ok(())
+    aoc_common::run(DAY, &Day07, config)
 }
 
-fn solve(contents: &str, problem_number: i32) -> Result<i32, Box<dyn Error>> {
+#[derive(Default)]
+pub struct Day07;
 
-    if problem_number == 1 {
-        return sum_dir_under_n(contents, 100000);
-    } else {
-        return size_deleted(contents, 70000000, 30000000);
+impl Solution for Day07 {
+    type Input = HashMap<String, i32>;
+
+    fn parse(&self, contents: &str) -> Result<Self::Input, AocError> {
+        path_sizes(contents)
     }
 
-}
+    fn part1(&self, input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(sum_dir_under_n(input, 100000)?.into())
+    }
 
-fn size_deleted(contents: &str, max_storage: i32, update_size: i32) -> Result<i32, Box<dyn Error>> {
+    fn part2(&self, input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(size_deleted(input, 70000000, 30000000)?.into())
+    }
+}
 
-    let sizes = path_sizes(contents, false)?;
-    let used = max_storage - sizes.values().sum::<i32>();
-    let to_delete = update_size - used;
+fn size_deleted(sizes: &HashMap<String, i32>, max_storage: i32, update_size: i32) -> Result<i32, AocError> {
 
-    dbg!(used);
-    dbg!(&to_delete);
+    // sizes are rolled up so the root dir holds everything in use
+    let used = sizes.get("/").copied().unwrap_or_default();
+    let free = max_storage - used;
+    let to_delete = update_size - free;
 
     let mut candidate = &max_storage; 
     for (_k, v) in sizes.iter() {
-        dbg!(v);
         if *v >= to_delete && v < candidate {
             candidate = v; 
         }
@@ -59,9 +49,7 @@ fn size_deleted(contents: &str, max_storage: i32, update_size: i32) -> Result<i3
 
 // should solve by keeping list of dirs where each dir contains a ptr to its children
 // but idk how to build a map/graph in rust with borrow checker
-fn sum_dir_under_n(contents: &str, arg: i32) -> Result<i32, Box<dyn Error>> {
-
-    let sizes = path_sizes(contents, true)?;
+fn sum_dir_under_n(sizes: &HashMap<String, i32>, arg: i32) -> Result<i32, AocError> {
 
     let mut result = 0;
     for (_k, v) in sizes.iter() {
@@ -73,13 +61,17 @@ fn sum_dir_under_n(contents: &str, arg: i32) -> Result<i32, Box<dyn Error>> {
     Ok(result)
 }
 
-fn path_sizes(contents: &str, rolled_up: bool) -> Result<HashMap<String, i32> ,Box<dyn Error>> {
+fn path_sizes(contents: &str) -> Result<HashMap<String, i32>, AocError> {
     let mut size = HashMap::new();
     let mut path: Vec<&str> = Vec::new();
 
-    for line in contents.lines() {
-        if line.starts_with("$") && line.contains("cd") {
-            let dir = line.rsplitn(2, ' ').nth(0).unwrap();
+    for line in input_lines(DAY, contents) {
+        let text = line.text;
+        if text.starts_with('$') && text.contains("cd") {
+            let dir = match text.rsplit_once(' ') {
+                Some((_, dir)) => dir,
+                None => return Err(line.error(text, "cd needs a directory")),
+            };
 
             if dir == ".." {
                 path.pop();
@@ -93,25 +85,21 @@ fn path_sizes(contents: &str, rolled_up: bool) -> Result<HashMap<String, i32> ,B
             path.push(dir);
 
         }
-        else if line.starts_with("$") && line.contains("ls") {
-            continue;
-        } else if line.starts_with("dir") {
+        else if (text.starts_with('$') && text.contains("ls")) || text.starts_with("dir") {
             continue;
         }
         else {
-            let file_size = line.split(' ').nth(0).unwrap().parse::<i32>().unwrap();
-
-            if rolled_up { 
-                // I know I know 
-                let mut p = path.clone();
-                while !p.is_empty() {
-                    let key = p.join(" ");
-                    size.entry(key).and_modify(|counter| *counter += file_size).or_insert(file_size);
-                    p.pop();
-                }
-            } else {
-                let key = path.join(" ");
+            let file_size = match text.split_once(' ') {
+                Some((num, _)) => line.parse_num::<i32>(num)?,
+                None => return Err(line.error(text, "expected a file size and name")),
+            };
+
+            // I know I know 
+            let mut p = path.clone();
+            while !p.is_empty() {
+                let key = p.join(" ");
                 size.entry(key).and_modify(|counter| *counter += file_size).or_insert(file_size);
+                p.pop();
             }
         }
     }
@@ -128,12 +116,12 @@ mod tests {
         let input = "$ cd /\n$ ls\ndir a\n14848514 b.txt\n8504156 c.dat\ndir d\n$ cd a\n$ ls\ndir e\n29116 f\n2557 g\n62596 h.lst\n$ cd e\n$ ls\n584 i\n$ cd ..\n$ cd ..\n$ cd d\n$ ls\n4060174 j\n8033020 d.log\n5626152 d.ext\n7214296 k";
         let p = 1;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day07.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return -1
+            Answer::Int(-1)
         });
 
-        assert_eq!(95437, result);
+        assert_eq!(Answer::Int(95437), result);
     }
 
     #[test]
@@ -141,11 +129,23 @@ mod tests {
         let input = "$ cd /\n$ ls\ndir a\n14848514 b.txt\n8504156 c.dat\ndir d\n$ cd a\n$ ls\ndir e\n29116 f\n2557 g\n62596 h.lst\n$ cd e\n$ ls\n584 i\n$ cd ..\n$ cd ..\n$ cd d\n$ ls\n4060174 j\n8033020 d.log\n5626152 d.ext\n7214296 k";
 
         let p = 2;
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day07.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return -1
+            Answer::Int(-1)
         });
 
-        assert_eq!(24933642, result);
+        assert_eq!(Answer::Int(24933642), result);
+    }
+
+    #[test]
+    fn bad_file_size() {
+        let input = "$ cd /\n$ ls\n14848514 b.txt\n85o4156 c.dat";
+
+        let err = Day07.solve(input, 1).unwrap_err();
+
+        assert_eq!(
+            "day 07, line 4, column 1: expected a number (found \"85o4156\")",
+            err.to_string()
+        );
     }
 }
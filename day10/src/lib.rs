@@ -1,43 +1,35 @@
-use std::fs;
 use std::error::Error;
+
+pub use aoc_common::Config;
+use aoc_common::{input_lines, Answer, AocError, Solution};
 use nom::{character::complete::alpha1, IResult};
 
-pub struct Config {
-    file_path: String,
-    problem_number: i32,
-}
-
-impl Config {
-    pub fn build(args: &[String]) -> Result<Config, &'static str> {
-        if args.len() < 3 {
-            return Err("must provide input file_path and which problem to solve")
-        }
-        let file_path = args[1].clone();
-        let problem_number = args[2].clone().parse::<i32>().unwrap_or_default();
-
-        Ok(Config { file_path, problem_number })
-    }
-}
+const DAY: usize = 10;
 
 pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(config.file_path)?;
-    let result = solve(&contents, config.problem_number)?;
-    println!("result ->\n{result}");
-    Ok(())
+    aoc_common::run(DAY, &Day10, config)
 }
 
-fn solve(contents: &str, problem_number: i32) -> Result<String, Box<dyn Error>> {
+#[derive(Default)]
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<i32>;
+
+    fn parse(&self, contents: &str) -> Result<Self::Input, AocError> {
+        calc_cycle_values(contents)
+    }
 
-    if problem_number == 1 {
-        return sum_signal_strength(contents);
-    } else {
-        return draw(contents);
+    fn part1(&self, input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(sum_signal_strength(input)?.into())
     }
 
+    fn part2(&self, input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(Answer::Image(draw(input)?))
+    }
 }
 
-fn draw(contents: &str) -> Result<String, Box<dyn Error>> {
-    let cycle_values: Vec<i32> = calc_cycle_values(contents);
+fn draw(cycle_values: &[i32]) -> Result<Vec<String>, AocError> {
     let mut screen = vec!['.'; cycle_values.len()+1];
 
     for (i, val) in cycle_values.iter().enumerate() {
@@ -47,7 +39,7 @@ fn draw(contents: &str) -> Result<String, Box<dyn Error>> {
         }
     }
 
-    let img: String = screen.chunks(40).map(|row| row.iter().collect::<String>() + "\n").collect();
+    let img: Vec<String> = screen.chunks(40).map(|row| row.iter().collect::<String>()).collect();
 
     Ok(img)
 }
@@ -58,9 +50,7 @@ struct Instr {
     amt: i32,
 }
 
-fn sum_signal_strength(contents: &str) -> Result<String, Box<dyn Error>> {
-    let cycle_value: Vec<i32> = calc_cycle_values(contents);
-
+fn sum_signal_strength(cycle_value: &[i32]) -> Result<i32, AocError> {
     let mut result = 0;
     let mut i = 19;
 
@@ -69,17 +59,15 @@ fn sum_signal_strength(contents: &str) -> Result<String, Box<dyn Error>> {
         i += 40;
     }
 
-    let res_str = result.to_string();
-
-    Ok(res_str)
+    Ok(result)
 }
 
-fn calc_cycle_values(contents: &str) -> Vec<i32> {
+fn calc_cycle_values(contents: &str) -> Result<Vec<i32>, AocError> {
     let mut x_value: i32 = 1;
     let mut cycle_value: Vec<i32> = Vec::new();
     let mut instructions: Vec<Instr> = Vec::new();
 
-    for (instr_num, line) in contents.lines().enumerate() {
+    for (instr_num, line) in input_lines(DAY, contents).enumerate() {
         if instr_num > 0 {
             cycle_value.push(x_value);
         }
@@ -92,17 +80,19 @@ fn calc_cycle_values(contents: &str) -> Vec<i32> {
         }
 
 
-        let (amt_raw, instr) = parse(line).unwrap();
-        let amt = amt_raw.trim().parse::<i32>().unwrap_or(0);
-        if instr == "noop" {
-            continue;
-        } else {
-            instructions.push(Instr { cycles: 1, amt })
+        let (amt_raw, instr) = match parse(line.text) {
+            Ok(parsed) => parsed,
+            Err(_) => return Err(line.error(line.text, "expected an instruction")),
+        };
+        match instr {
+            "noop" => continue,
+            "addx" => instructions.push(Instr { cycles: 1, amt: line.parse_num(amt_raw)? }),
+            _ => return Err(line.error(instr, "unknown instruction")),
         }
         cycle_value.push(x_value);
     }
 
-    return cycle_value;
+    Ok(cycle_value)
 }
 
 fn parse(i: &str) -> IResult<&str, &str> {
@@ -263,12 +253,12 @@ noop
 noop";
         let p = 1;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day10.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return "".to_string() 
+            Answer::Int(-1)
         });
 
-        assert_eq!("13140", result);
+        assert_eq!(Answer::Int(13140), result);
     }
 
     #[test]
@@ -421,17 +411,30 @@ noop
 noop";
         let p = 2;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day10.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return "".to_string()
+            Answer::Image(vec![])
         });
 
-        assert_eq!("##..##..##..##..##..##..##..##..##..##..
+        let expected = "##..##..##..##..##..##..##..##..##..##..
 ###...###...###...###...###...###...###.
 ####....####....####....####....####....
 #####.....#####.....#####.....#####.....
 ######......######......######......####
 #######.......#######.......#######.....
-", result);
+";
+        assert_eq!(Answer::Image(expected.lines().map(String::from).collect()), result);
+    }
+
+    #[test]
+    fn missing_amount() {
+        let input = "noop\naddx 3\naddx";
+
+        let err = Day10.solve(input, 1).unwrap_err();
+
+        assert_eq!(
+            "day 10, line 3, column 5: expected a number (found \"\")",
+            err.to_string()
+        );
     }
 }
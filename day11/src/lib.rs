@@ -1,10 +1,10 @@
 use std::error::Error;
-use std::fs;
 
-pub struct Config {
-    file_path: String,
-    problem_number: i128,
-}
+pub use aoc_common::Config;
+use aoc_common::{input_lines, Answer, AocError, InputLine, Solution};
+
+const DAY: usize = 11;
+
 #[derive(Debug, Default, Clone, Copy)]
 enum Op {
     #[default]
@@ -13,7 +13,7 @@ enum Op {
 }
 
 #[derive(Debug, Default, Clone)]
-struct Monkey {
+pub struct Monkey {
     items: Vec<i128>,
     operation: (Op, i128, bool),
     test_denom: i128,
@@ -22,18 +22,21 @@ struct Monkey {
 }
 
 impl Monkey {
-    pub fn new(monkey_desc: &str) -> Result<Self, Box<dyn Error>> {
+    // desc holds the lines following a "Monkey N:" header
+    pub fn new(desc: &[InputLine]) -> Result<Self, AocError> {
         let mut new_monkey = Monkey::default();
 
-        let desc_parts: Vec<&str> = monkey_desc.split("\n").map(|p| p.trim()).collect();
-        for p in desc_parts {
-            let instr: Vec<&str> = p.split(":").collect();
-            match instr[0] {
-                "Starting items" => new_monkey.parse_starting_items(instr)?,
-                "Operation" => new_monkey.parse_operation(instr)?,
-                "Test" => new_monkey.parse_test(instr)?,
-                "If true" => new_monkey.parse_receiver(instr, true)?,
-                "If false" => new_monkey.parse_receiver(instr, false)?,
+        for line in desc {
+            let (key, value) = match line.text.split_once(':') {
+                Some(parts) => parts,
+                None => continue,
+            };
+            match key.trim() {
+                "Starting items" => new_monkey.parse_starting_items(*line, value)?,
+                "Operation" => new_monkey.parse_operation(*line, value)?,
+                "Test" => new_monkey.parse_test(*line, value)?,
+                "If true" => new_monkey.parse_receiver(*line, value, true)?,
+                "If false" => new_monkey.parse_receiver(*line, value, false)?,
                 _ => continue,
             }
         }
@@ -41,130 +44,154 @@ impl Monkey {
         Ok(new_monkey)
     }
 
-    fn parse_starting_items(&mut self, starting_items: Vec<&str>) -> Result<(), Box<dyn Error>> {
-        if starting_items.len() < 2 {
-            Err("starting items did not contain list of numbers")?
+    fn parse_starting_items(
+        &mut self,
+        line: InputLine,
+        starting_items: &str,
+    ) -> Result<(), AocError> {
+        if starting_items.trim().is_empty() {
+            return Ok(());
         }
 
-        self.items = starting_items[1]
-            .split(",")
-            .map(|item| item.trim().parse::<i128>().unwrap())
-            .collect();
+        self.items = starting_items
+            .split(',')
+            .map(|item| line.parse_num::<i128>(item))
+            .collect::<Result<Vec<i128>, AocError>>()?;
 
         Ok(())
     }
 
-    fn parse_operation(&mut self, operation: Vec<&str>) -> Result<(), Box<dyn Error>> {
-        if operation.len() < 2 {
-            Err("starting items did not contain list of numbers")?
+    fn parse_operation(&mut self, line: InputLine, operation: &str) -> Result<(), AocError> {
+        let parts: Vec<&str> = operation.split_whitespace().collect();
+        if parts.len() != 5 || parts[0] != "new" || parts[1] != "=" || parts[2] != "old" {
+            return Err(line.error(
+                operation.trim(),
+                "operation must look like new = old OP AMT",
+            ));
         }
 
-        let mut op = Op::default();
-        let mut o = "*".to_string();
-        if operation[1].contains("*") {
-            op = Op::Mult;
-        } else if operation[1].contains("+") {
-            op = Op::Add;
-            o = "+".to_string();
-        }
+        let op = match parts[3] {
+            "*" => Op::Mult,
+            "+" => Op::Add,
+            _ => return Err(line.error(parts[3], "operator must be * or +")),
+        };
 
         let mut me = bool::default();
-        let amt = operation[1]
-            .split(&o)
-            .map(|i| i.trim())
-            .nth(1)
-            .unwrap()
-            .parse::<i128>()
-            .unwrap_or_else(|_| {
-                me = true;
-                i128::default()
-            });
+        let amt = if parts[4] == "old" {
+            me = true;
+            i128::default()
+        } else {
+            line.parse_num::<i128>(parts[4])?
+        };
 
         self.operation = (op, amt, me);
         Ok(())
     }
 
-    fn parse_test(&mut self, test: Vec<&str>) -> Result<(), Box<dyn Error>> {
-        if test.len() < 2 {
-            Err("starting items did not contain list of numbers")?
-        }
+    fn parse_test(&mut self, line: InputLine, test: &str) -> Result<(), AocError> {
+        let denom = match test.split_whitespace().nth(2) {
+            Some(denom) => denom,
+            None => return Err(line.error(test.trim(), "test must look like divisible by N")),
+        };
 
-        self.test_denom = test[1].split(" ").nth(3).unwrap().parse::<i128>().unwrap();
+        self.test_denom = line.parse_num::<i128>(denom)?;
+        if self.test_denom == 0 {
+            return Err(line.error(denom, "cannot test divisibility by zero"));
+        }
 
         Ok(())
     }
 
-    fn parse_receiver(&mut self, receiver: Vec<&str>, pos: bool) -> Result<(), Box<dyn Error>> {
-        if receiver.len() < 2 {
-            Err("starting items did not contain list of numbers")?
-        }
+    fn parse_receiver(
+        &mut self,
+        line: InputLine,
+        receiver: &str,
+        pos: bool,
+    ) -> Result<(), AocError> {
+        let target = match receiver.split_whitespace().nth(3) {
+            Some(target) => line.parse_num::<usize>(target)?,
+            None => {
+                return Err(line.error(receiver.trim(), "receiver must look like throw to monkey N"))
+            }
+        };
 
         if pos {
-            self.pos_receiver = receiver[1]
-                .split(" ")
-                .nth(4)
-                .unwrap()
-                .parse::<usize>()
-                .unwrap();
+            self.pos_receiver = target;
         } else {
-            self.neg_receiver = receiver[1]
-                .split(" ")
-                .nth(4)
-                .unwrap()
-                .parse::<usize>()
-                .unwrap();
+            self.neg_receiver = target;
         }
 
         Ok(())
     }
 }
 
-impl Config {
-    pub fn build(args: &[String]) -> Result<Config, &'static str> {
-        if args.len() < 3 {
-            return Err("must provide input file_path and which problem to solve");
+pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
+    aoc_common::run(DAY, &Day11, config)
+}
+
+#[derive(Default)]
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<Monkey>;
+
+    fn parse(&self, contents: &str) -> Result<Self::Input, AocError> {
+        let mut descs: Vec<Vec<InputLine>> = Vec::new();
+        for line in input_lines(DAY, contents) {
+            if line.text.trim().starts_with("Monkey") {
+                descs.push(Vec::new());
+            } else if let Some(desc) = descs.last_mut() {
+                desc.push(line);
+            }
+        }
+
+        let monkeys = descs
+            .iter()
+            .map(|desc| Monkey::new(desc))
+            .collect::<Result<Vec<Monkey>, AocError>>()?;
+
+        for (i, monkey) in monkeys.iter().enumerate() {
+            for target in [monkey.pos_receiver, monkey.neg_receiver] {
+                if target >= monkeys.len() {
+                    return Err(AocError::unsolvable(
+                        DAY,
+                        format!("monkey {i} throws to monkey {target}, which does not exist"),
+                    ));
+                }
+            }
         }
-        let file_path = args[1].clone();
-        let problem_number = args[2].clone().parse::<i128>().unwrap_or_default();
 
-        Ok(Config {
-            file_path,
-            problem_number,
-        })
+        Ok(monkeys)
     }
-}
 
-pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(config.file_path)?;
-    let result = solve(&contents, config.problem_number)?;
-    println!("result -> {result}");
-    Ok(())
-}
+    fn part1(&self, input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(monkey_business(input.clone(), 20, true).into())
+    }
 
-fn solve(contents: &str, problem_number: i128) -> Result<i128, Box<dyn Error>> {
-    if problem_number == 1 {
-        let mut inspect_cnts = monkey_inspection_counts(contents);
-        inspect_cnts.sort();
-        let l = inspect_cnts.len();
-        Ok(inspect_cnts[l - 1].clone() * inspect_cnts[l - 2].clone())
-    } else {
-        Ok(i128::default())
+    fn part2(&self, input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(monkey_business(input.clone(), 10000, false).into())
     }
 }
 
-fn monkey_inspection_counts(contents: &str) -> Vec<i128> {
-    let mut monkeys: Vec<Monkey> = contents
-        .trim()
-        .split("Monkey")
-        .map(|md| Monkey::new(md).unwrap())
-        .collect();
-
-    monkeys.remove(0);
+fn monkey_business(monkeys: Vec<Monkey>, rounds: usize, relief: bool) -> i128 {
+    let mut inspect_cnts = monkey_inspection_counts(monkeys, rounds, relief);
+    inspect_cnts.sort();
+    let l = inspect_cnts.len();
+    if l < 2 {
+        return inspect_cnts.iter().product();
+    }
+    inspect_cnts[l - 1] * inspect_cnts[l - 2]
+}
 
+fn monkey_inspection_counts(mut monkeys: Vec<Monkey>, rounds: usize, relief: bool) -> Vec<i128> {
     let mut inspect_cnts = vec![i128::default(); monkeys.len()];
 
-    for round in 0..10000 {
-        println!("round: {}", round);
+    // https://mathworld.wolfram.com/Congruence.html
+    // 11. a=b (mod m_1) and a=b (mod m_2)=>a=b (mod [m_1,m_2]), where [m_1,m_2] is the least common multiple.
+    // the test denominators are all prime so their product is the lcm
+    let modulus: i128 = monkeys.iter().map(|m| m.test_denom).product();
+
+    for _round in 0..rounds {
         for monkey_num in 0..monkeys.len() {
             let items = monkeys[monkey_num].items.clone();
             for mut item in items {
@@ -181,9 +208,11 @@ fn monkey_inspection_counts(contents: &str) -> Vec<i128> {
                 }
 
                 // monkey get bored
-                // https://mathworld.wolfram.com/Congruence.html
-                // 11. a=b (mod m_1) and a=b (mod m_2)=>a=b (mod [m_1,m_2]), where [m_1,m_2] is the least common multiple.
-                item = item % 9699690;
+                if relief {
+                    item /= 3;
+                } else {
+                    item %= modulus;
+                }
 
                 let p = monkeys[monkey_num].pos_receiver;
                 let n = monkeys[monkey_num].neg_receiver;
@@ -199,7 +228,7 @@ fn monkey_inspection_counts(contents: &str) -> Vec<i128> {
         }
     }
 
-    return inspect_cnts;
+    inspect_cnts
 }
 
 #[cfg(test)]
@@ -238,24 +267,67 @@ mod tests {
           If false: throw to monkey 1";
         let p = 1;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day11.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return 1;
+            Answer::BigInt(1)
         });
 
-        assert_eq!(10605, result);
+        assert_eq!(Answer::BigInt(10605), result);
     }
 
-    // #[test]
-    // fn p2() {
-    //     let input = "$ cd /\n$ ls\ndir a\n14848514 b.txt\n8504156 c.dat\ndir d\n$ cd a\n$ ls\ndir e\n29116 f\n2557 g\n62596 h.lst\n$ cd e\n$ ls\n584 i\n$ cd ..\n$ cd ..\n$ cd d\n$ ls\n4060174 j\n8033020 d.log\n5626152 d.ext\n7214296 k";
+    #[test]
+    fn p2() {
+        let input = "Monkey 0:
+        Starting items: 79, 98
+        Operation: new = old * 19
+        Test: divisible by 23
+          If true: throw to monkey 2
+          If false: throw to monkey 3
+      
+      Monkey 1:
+        Starting items: 54, 65, 75, 74
+        Operation: new = old + 6
+        Test: divisible by 19
+          If true: throw to monkey 2
+          If false: throw to monkey 0
+      
+      Monkey 2:
+        Starting items: 79, 60, 97
+        Operation: new = old * old
+        Test: divisible by 13
+          If true: throw to monkey 1
+          If false: throw to monkey 3
+      
+      Monkey 3:
+        Starting items: 74
+        Operation: new = old + 3
+        Test: divisible by 17
+          If true: throw to monkey 0
+          If false: throw to monkey 1";
+        let p = 2;
+
+        let result = Day11.solve(input, p).unwrap_or_else(|err| {
+            println!("failed to unwrap test result, {err}");
+            Answer::BigInt(1)
+        });
 
-    //     let p = 2;
-    //     let result = solve(input, p).unwrap_or_else(|err| {
-    //         println!("failed to unwrap test result, {err}");
-    //         return -1;
-    //     });
+        assert_eq!(Answer::BigInt(2713310158), result);
+    }
 
-    //     assert_eq!(24933642, result);
-    // }
+    #[test]
+    fn bad_operation() {
+        let input = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old / 19
+  Test: divisible by 23
+    If true: throw to monkey 0
+    If false: throw to monkey 0";
+
+        let err = Day11.solve(input, 1).unwrap_err();
+
+        assert_eq!(
+            "day 11, line 3, column 24: operator must be * or + (found \"/\")",
+            err.to_string()
+        );
+    }
 }
@@ -1,212 +1,129 @@
 use std::collections::{HashSet, VecDeque};
 use std::error::Error;
-use std::fs;
 
-pub struct Config {
-    file_path: String,
-    problem_number: i32,
-}
+pub use aoc_common::Config;
+use aoc_common::{Answer, AocError, Grid, Point, Solution};
 
-impl Config {
-    pub fn build(args: &[String]) -> Result<Config, &'static str> {
-        if args.len() < 3 {
-            return Err("must provide input file_path and which problem to solve");
-        }
-        let file_path = args[1].clone();
-        let problem_number = args[2].clone().parse::<i32>().unwrap_or_default();
+const DAY: usize = 12;
 
-        Ok(Config {
-            file_path,
-            problem_number,
-        })
-    }
+pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
+    aoc_common::run(DAY, &Day12, config)
 }
 
-#[derive(Debug, Clone, Copy)]
-struct Point {
-    i: usize,
-    j: usize,
-    depth: i32,
-}
+#[derive(Default)]
+pub struct Day12;
 
-pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(config.file_path)?;
-    let result = solve(&contents, config.problem_number)?;
-    println!("result -> {result}");
-    Ok(())
-}
+impl Solution for Day12 {
+    type Input = Grid<char>;
+
+    fn parse(&self, contents: &str) -> Result<Self::Input, AocError> {
+        make_grid(contents)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(shortest_path(input)?.into())
+    }
 
-fn solve(contents: &str, problem_number: i32) -> Result<i32, Box<dyn Error>> {
-    if problem_number == 1 {
-        return shortest_path(contents);
-    } else {
-        return shorted_viable_path(contents);
+    fn part2(&self, input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(shorted_viable_path(input)?.into())
     }
 }
 
-fn shorted_viable_path(contents: &str) -> Result<i32, Box<dyn Error>> {
-    let mut grid = make_grid(contents);
+fn shorted_viable_path(grid: &Grid<char>) -> Result<i32, AocError> {
+    let mut grid = grid.clone();
     let (starts, target) = find_viable_positions(&mut grid);
 
     let mut result = i32::MAX;
     for start in starts {
-        let cnt = search(
-            &mut grid,
-            Point {
-                i: start.0,
-                j: start.1,
-                depth: 1,
-            },
-            target,
-        );
+        let cnt = search(&grid, start, target);
 
         if cnt != -1 && cnt < result {
             result = cnt;
         }
     }
 
+    if result == i32::MAX {
+        return Err(AocError::unsolvable(DAY, "no path reaches the best signal"));
+    }
+
     Ok(result)
 }
 
-fn shortest_path(contents: &str) -> Result<i32, Box<dyn Error>> {
-    let mut grid = make_grid(contents);
+fn shortest_path(grid: &Grid<char>) -> Result<i32, AocError> {
+    let mut grid = grid.clone();
     let (start, target) = find_positions(&mut grid);
-    let cnt = search(&mut grid, start, target);
+    let cnt = search(&grid, start, target);
+    if cnt == -1 {
+        return Err(AocError::unsolvable(DAY, "no path reaches the best signal"));
+    }
+
     Ok(cnt)
 }
 
-fn search(grid: &Vec<Vec<char>>, start: Point, target: Point) -> i32 {
-    let mut to_visit = VecDeque::from([start]);
-    let mut seen: HashSet<(usize, usize)> = HashSet::from([(start.i, start.j)]);
-
-    while !to_visit.is_empty() {
-        let curr_pt = to_visit.pop_front().unwrap();
+fn search(grid: &Grid<char>, start: Point, target: Point) -> i32 {
+    let mut to_visit = VecDeque::from([(start, 0)]);
+    let mut seen: HashSet<Point> = HashSet::from([start]);
 
-        if curr_pt.i == target.i && curr_pt.j == target.j {
-            return curr_pt.depth - 1;
+    while let Some((curr_pt, depth)) = to_visit.pop_front() {
+        if curr_pt == target {
+            return depth;
         }
 
         // look around
-        if !curr_pt.i.checked_sub(1).is_none()
-            && is_climbable(grid[curr_pt.i][curr_pt.j], grid[curr_pt.i - 1][curr_pt.j])
-            && !seen.contains(&(curr_pt.i - 1, curr_pt.j))
-        {
-            seen.insert((curr_pt.i - 1, curr_pt.j));
-            to_visit.push_back(Point {
-                i: curr_pt.i - 1,
-                j: curr_pt.j,
-                depth: curr_pt.depth + 1,
-            })
-        }
-
-        if !curr_pt.j.checked_sub(1).is_none()
-            && is_climbable(grid[curr_pt.i][curr_pt.j], grid[curr_pt.i][curr_pt.j - 1])
-            && !seen.contains(&(curr_pt.i, curr_pt.j - 1))
-        {
-            seen.insert((curr_pt.i, curr_pt.j - 1));
-            to_visit.push_back(Point {
-                i: curr_pt.i,
-                j: curr_pt.j - 1,
-                depth: curr_pt.depth + 1,
-            })
-        }
-
-        if curr_pt.i + 1 < grid.len()
-            && is_climbable(grid[curr_pt.i][curr_pt.j], grid[curr_pt.i + 1][curr_pt.j])
-            && !seen.contains(&(curr_pt.i + 1, curr_pt.j))
-        {
-            seen.insert((curr_pt.i + 1, curr_pt.j));
-            to_visit.push_back(Point {
-                i: curr_pt.i + 1,
-                j: curr_pt.j,
-                depth: curr_pt.depth + 1,
-            })
-        }
-
-        if curr_pt.j + 1 < grid[0].len()
-            && is_climbable(grid[curr_pt.i][curr_pt.j], grid[curr_pt.i][curr_pt.j + 1])
-            && !seen.contains(&(curr_pt.i, curr_pt.j + 1))
-        {
-            seen.insert((curr_pt.i, curr_pt.j + 1));
-            to_visit.push_back(Point {
-                i: curr_pt.i,
-                j: curr_pt.j + 1,
-                depth: curr_pt.depth + 1,
-            })
+        for next_pt in grid.neighbours4(curr_pt) {
+            if is_climbable(grid[curr_pt], grid[next_pt]) && seen.insert(next_pt) {
+                to_visit.push_back((next_pt, depth + 1));
+            }
         }
     }
 
-    return -1;
+    -1
 }
 
 // next can be at most 1 greater than current
 fn is_climbable(curr: char, next: char) -> bool {
-    if next <= char::from_u32(curr as u32 + 1).unwrap() {
+    if next as u32 <= curr as u32 + 1 {
         return true;
     }
-    return false;
+    false
 }
 
-fn make_grid(contents: &str) -> Vec<Vec<char>> {
-    contents
-        .lines()
-        .map(|line| line.trim().chars().collect::<Vec<char>>())
-        .collect::<Vec<Vec<char>>>()
+fn make_grid(contents: &str) -> Result<Grid<char>, AocError> {
+    let grid = Grid::parse(DAY, contents, "elevation must be a-z, S or E", |c| {
+        matches!(c, 'a'..='z' | 'S' | 'E').then_some(c)
+    })?;
+
+    let starts = grid.iter().filter(|(_, c)| **c == 'S').count();
+    let targets = grid.iter().filter(|(_, c)| **c == 'E').count();
+    if starts != 1 || targets != 1 {
+        return Err(AocError::unsolvable(
+            DAY,
+            "map must have exactly one start S and one target E",
+        ));
+    }
+
+    Ok(grid)
 }
 
-fn find_positions(grid: &mut Vec<Vec<char>>) -> (Point, Point) {
-    let (mut start, mut target) = ((0, 0), (0, 0));
-    for (i, row) in grid.iter_mut().enumerate() {
-        for (j, spot) in row.iter_mut().enumerate() {
-            if *spot == 'S' {
-                *spot = 'a';
-                start = (i, j);
-            } else if *spot == 'E' {
-                *spot = 'z';
-                target = (i, j);
-            }
-        }
-    }
+// make_grid already checked that S and E are both on the map
+fn find_positions(grid: &mut Grid<char>) -> (Point, Point) {
+    let start = grid.find(|c| *c == 'S').unwrap_or_default();
+    let target = grid.find(|c| *c == 'E').unwrap_or_default();
+    grid[start] = 'a';
+    grid[target] = 'z';
 
-    return (
-        Point {
-            i: start.0,
-            j: start.1,
-            depth: 1,
-        },
-        Point {
-            i: target.0,
-            j: target.1,
-            depth: -1,
-        },
-    );
+    (start, target)
 }
 
-fn find_viable_positions(grid: &mut Vec<Vec<char>>) -> (Vec<(usize, usize)>, Point) {
-    let mut starts: Vec<(usize, usize)> = Vec::new();
-    let mut target: (usize, usize) = (0, 0);
-    for (i, row) in grid.iter_mut().enumerate() {
-        for (j, spot) in row.iter_mut().enumerate() {
-            if *spot == 'a' {
-                starts.push((i, j));
-            } else if *spot == 'S' {
-                *spot = 'a';
-                starts.push((i, j));
-            } else if *spot == 'E' {
-                *spot = 'z';
-                target = (i, j);
-            }
-        }
-    }
+fn find_viable_positions(grid: &mut Grid<char>) -> (Vec<Point>, Point) {
+    let (_, target) = find_positions(grid);
+    let starts = grid
+        .iter()
+        .filter(|(_, c)| **c == 'a')
+        .map(|(p, _)| p)
+        .collect();
 
-    return (
-        starts,
-        Point {
-            i: target.0,
-            j: target.1,
-            depth: -1,
-        },
-    );
+    (starts, target)
 }
 
 #[cfg(test)]
@@ -223,12 +140,12 @@ mod tests {
         abdefghi";
         let p = 1;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day12.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return 1;
+            Answer::Int(1)
         });
 
-        assert_eq!(31, result);
+        assert_eq!(Answer::Int(31), result);
     }
 
     #[test]
@@ -240,11 +157,25 @@ mod tests {
         abdefghi";
 
         let p = 2;
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day12.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return -1;
+            Answer::Int(-1)
         });
 
-        assert_eq!(29, result);
+        assert_eq!(Answer::Int(29), result);
+    }
+
+    #[test]
+    fn bad_elevation() {
+        let input = "Sabqponm
+        abcryxxl
+        accs?Exk";
+
+        let err = Day12.solve(input, 1).unwrap_err();
+
+        assert_eq!(
+            "day 12, line 3, column 13: elevation must be a-z, S or E (found \"?\")",
+            err.to_string()
+        );
     }
 }
@@ -1,32 +1,15 @@
 use std::cmp::Ordering;
 use std::error::Error;
-use std::fs;
 
+pub use aoc_common::Config;
+use aoc_common::{input_lines, Answer, AocError, Solution};
 use serde::Deserialize;
 
-pub struct Config {
-    file_path: String,
-    problem_number: i32,
-}
-
-impl Config {
-    pub fn build(args: &[String]) -> Result<Config, &'static str> {
-        if args.len() < 3 {
-            return Err("must provide input file_path and which problem to solve");
-        }
-        let file_path = args[1].clone();
-        let problem_number = args[2].clone().parse::<i32>().unwrap_or_default();
-
-        Ok(Config {
-            file_path,
-            problem_number,
-        })
-    }
-}
+const DAY: usize = 13;
 
 #[derive(Deserialize, Clone, PartialEq, Eq, Debug)]
 #[serde(untagged)]
-enum Packet {
+pub enum Packet {
     Number(u64),
     List(Vec<Packet>),
 }
@@ -49,51 +32,70 @@ impl PartialOrd for Packet {
 }
 
 pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(config.file_path)?;
-    let result = solve(&contents, config.problem_number)?;
-    println!("result -> {result}");
-    Ok(())
+    aoc_common::run(DAY, &Day13, config)
 }
 
-fn solve(contents: &str, problem_number: i32) -> Result<usize, Box<dyn Error>> {
-    if problem_number == 1 {
-        return sum_ordered_packet_indices(contents);
-    } else {
-        return ordered_divider_indices(contents);
+#[derive(Default)]
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<Packet>;
+
+    fn parse(&self, contents: &str) -> Result<Self::Input, AocError> {
+        let mut packets = Vec::new();
+        for line in input_lines(DAY, contents).filter(|l| !l.text.is_empty()) {
+            match serde_json::from_str::<Packet>(line.text) {
+                Ok(packet) => packets.push(packet),
+                // serde already knows where in the line it gave up
+                Err(err) => {
+                    return Err(AocError::Parse {
+                        day: DAY,
+                        line: line.line,
+                        column: err.column().max(1),
+                        text: line.text.to_string(),
+                        reason: "invalid packet".to_string(),
+                    })
+                }
+            }
+        }
+
+        Ok(packets)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(sum_ordered_packet_indices(input)?.into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(ordered_divider_indices(input)?.into())
     }
 }
 
-fn ordered_divider_indices(contents: &str) -> Result<usize, Box<dyn Error>> {
-    let mut packets: Vec<Packet> = contents
-        .split("\n")
-        .filter(|l| *l != "")
-        .map(|l| serde_json::from_str::<Packet>(l).unwrap())
-        .collect();
+fn ordered_divider_indices(packets: &[Packet]) -> Result<usize, AocError> {
+    let mut packets = packets.to_vec();
 
-    let divider1 = serde_json::from_str::<Packet>("[[2]]").unwrap();
-    let divider2 = serde_json::from_str::<Packet>("[[6]]").unwrap();
+    let divider1 = Packet::List(vec![Packet::List(vec![Packet::Number(2)])]);
+    let divider2 = Packet::List(vec![Packet::List(vec![Packet::Number(6)])]);
     packets.push(divider1.clone());
     packets.push(divider2.clone());
 
     packets.sort();
 
-    let ind1 = packets.binary_search(&divider1).unwrap() + 1;
-    let ind2 = packets.binary_search(&divider2).unwrap() + 1;
+    // both dividers were just added, so the search always finds them
+    let ind1 = packets.binary_search(&divider1).unwrap_or_else(|i| i) + 1;
+    let ind2 = packets.binary_search(&divider2).unwrap_or_else(|i| i) + 1;
 
     Ok(ind1 * ind2)
 }
 
-fn sum_ordered_packet_indices(contents: &str) -> Result<usize, Box<dyn Error>> {
+fn sum_ordered_packet_indices(packets: &[Packet]) -> Result<usize, AocError> {
     let mut result = 0;
-    for (i, packets) in contents.split("\n\n").enumerate() {
-        let mut parsed_packets = packets
-            .lines()
-            .map(|l| serde_json::from_str::<Packet>(l).unwrap());
-
-        let first_packet = parsed_packets.next().unwrap();
-        let second_packet = parsed_packets.next().unwrap();
+    for (i, pair) in packets.chunks(2).enumerate() {
+        if pair.len() < 2 {
+            return Err(AocError::unsolvable(DAY, "packets must come in pairs"));
+        }
 
-        if first_packet < second_packet {
+        if pair[0] < pair[1] {
             result += 1 + i;
         }
     }
@@ -133,12 +135,12 @@ mod tests {
 [1,[2,[3,[4,[5,6,0]]]],8,10]";
         let p = 1;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day13.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return 1;
+            Answer::Int(1)
         });
 
-        assert_eq!(13, result);
+        assert_eq!(Answer::Int(13), result);
     }
 
     #[test]
@@ -153,12 +155,12 @@ mod tests {
 [[[]]]";
         let p = 1;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day13.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return 1;
+            Answer::Int(1)
         });
 
-        assert_eq!(6, result);
+        assert_eq!(Answer::Int(6), result);
     }
 
     #[test]
@@ -167,12 +169,12 @@ mod tests {
 [[1],4]";
         let p = 1;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day13.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return 1;
+            Answer::Int(1)
         });
 
-        assert_eq!(1, result);
+        assert_eq!(Answer::Int(1), result);
     }
 
     #[test]
@@ -181,12 +183,12 @@ mod tests {
 [[1],[4]]";
         let p = 1;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day13.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return 1;
+            Answer::Int(1)
         });
 
-        assert_eq!(0, result);
+        assert_eq!(Answer::Int(1), result);
     }
 
     #[test]
@@ -195,12 +197,12 @@ mod tests {
 [[1],2]";
         let p = 1;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day13.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return 1;
+            Answer::Int(1)
         });
 
-        assert_eq!(1, result);
+        assert_eq!(Answer::Int(0), result);
     }
 
     #[test]
@@ -209,12 +211,12 @@ mod tests {
 [[1],2]";
         let p = 1;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day13.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return 1;
+            Answer::Int(1)
         });
 
-        assert_eq!(1, result);
+        assert_eq!(Answer::Int(0), result);
     }
 
     #[test]
@@ -244,12 +246,24 @@ mod tests {
 [1,[2,[3,[4,[5,6,0]]]],8,10]";
         let p = 2;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day13.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return 1;
+            Answer::Int(1)
         });
 
-        assert_eq!(140, result);
+        assert_eq!(Answer::Int(140), result);
+    }
+
+    #[test]
+    fn bad_packet() {
+        let input = "[1,1,3,1,1]\n[1,1,5,1,1]\n\n[[1],[2,x]]\n[[1],4]";
+
+        let err = Day13.solve(input, 1).unwrap_err();
+
+        assert_eq!(
+            "day 13, line 4, column 9: invalid packet (found \"[[1],[2,x]]\")",
+            err.to_string()
+        );
     }
     // #[test]
     // fn p2() {
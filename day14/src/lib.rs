@@ -1,187 +1,161 @@
-use itertools::Itertools;
-use std::collections::HashSet;
 use std::error::Error;
-use std::fs;
 
-pub struct Config {
-    file_path: String,
-    problem_number: i32,
-}
+pub use aoc_common::Config;
+use aoc_common::{input_lines, Answer, AocError, Grid, Point, Solution, Vector};
 
-impl Config {
-    pub fn build(args: &[String]) -> Result<Config, &'static str> {
-        if args.len() < 3 {
-            return Err("must provide input file_path and which problem to solve");
-        }
-        let file_path = args[1].clone();
-        let problem_number = args[2].clone().parse::<i32>().unwrap_or_default();
-
-        Ok(Config {
-            file_path,
-            problem_number,
-        })
-    }
-}
+const DAY: usize = 14;
 
 pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(config.file_path)?;
-    let result = solve(&contents, config.problem_number)?;
-    println!("result -> {result}");
-    Ok(())
+    aoc_common::run(DAY, &Day14, config)
 }
 
 // immediate thoughts: building this seems ok,
 // stop condition seems to be when the only option is out of bounds
 // bounds should be determined by rock positions
 // can slide over a rock path with iterator windows (probably some fasterthanlime genius way but idk)
-fn solve(contents: &str, problem_number: i32) -> Result<usize, Box<dyn Error>> {
-    let (mut rock_set, max_i, max_j, min_j) = rock_set(contents);
-    if problem_number == 1 {
-        return how_much_sand(&mut rock_set, max_i, max_j, min_j);
-    } else {
-        return piled_sand(&mut rock_set, max_i + 2);
+#[derive(Default)]
+pub struct Day14;
+
+// the cave as a grid of '#' rock, 'o' sand and '.' air. it is wide enough for
+// part 2's pile, which can spread at most one column per row below the source
+#[derive(Debug, Clone)]
+pub struct Cave {
+    grid: Grid<char>,
+    source: Point,
+    // deepest rock, anything falling past it is lost to the abyss in part 1
+    max_y: i64,
+}
+
+impl Solution for Day14 {
+    type Input = Cave;
+
+    fn parse(&self, contents: &str) -> Result<Self::Input, AocError> {
+        build_cave(contents)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(how_much_sand(&mut input.clone())?.into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(piled_sand(&mut input.clone())?.into())
     }
 }
 
-fn piled_sand(
-    rock_set: &mut HashSet<(usize, usize)>,
-    floor: usize,
-) -> Result<usize, Box<dyn Error>> {
-    let mut sand_dropped = 1;
-
-    let mut full = false;
-    while !full {
-        // drop sand
-        let mut spot: (usize, usize) = (0, 500);
-
-        while 1 == 1 {
-            let next_spot = next_spot(spot, &rock_set);
-            if spot == next_spot || next_spot.0 == floor {
-                if next_spot == (0, 500) {
-                    full = true;
-                    break;
-                }
-                // can no longer move
-                rock_set.insert(spot);
-                sand_dropped += 1;
-                break;
-            }
-            spot = next_spot;
-        }
+fn piled_sand(cave: &mut Cave) -> Result<usize, AocError> {
+    let mut sand_dropped = 0;
+
+    while cave.grid[cave.source] == '.' {
+        let spot = drop_sand(cave);
+        cave.grid[spot] = 'o';
+        sand_dropped += 1;
     }
 
     Ok(sand_dropped)
 }
 
-fn how_much_sand(
-    rock_set: &mut HashSet<(usize, usize)>,
-    max_i: usize,
-    max_j: usize,
-    min_j: usize,
-) -> Result<usize, Box<dyn Error>> {
+fn how_much_sand(cave: &mut Cave) -> Result<usize, AocError> {
     let mut sand_dropped = 0;
 
-    let mut reached_bottom = false;
-    let mut reached_side = false;
-    while !reached_bottom && !reached_side {
-        // drop sand
-        let mut spot: (usize, usize) = (0, 500);
-
-        while 1 == 1 {
-            let next_spot = next_spot(spot, &rock_set);
-            if spot == next_spot {
-                // can no longer move
-                rock_set.insert(spot);
-                sand_dropped += 1;
-                break;
-            }
-            spot = next_spot;
-
-            if spot.0 == max_i {
-                reached_bottom = true;
-                break;
-            } else if spot.1 == max_j || spot.1 == min_j {
-                reached_side = true;
-                break;
-            }
+    // sand can fill up to the source before any reaches the abyss
+    while cave.grid[cave.source] == '.' {
+        let spot = drop_sand(cave);
+        if spot.y > cave.max_y {
+            break;
         }
+        cave.grid[spot] = 'o';
+        sand_dropped += 1;
     }
 
     Ok(sand_dropped)
 }
 
-fn next_spot(spot: (usize, usize), rock_set: &HashSet<(usize, usize)>) -> (usize, usize) {
-    // check below
-    if !rock_set.contains(&(spot.0 + 1, spot.1)) {
-        return (spot.0 + 1, spot.1);
-    } else if !rock_set.contains(&(spot.0 + 1, spot.1 - 1)) {
-        return (spot.0 + 1, spot.1 - 1);
-    } else if !rock_set.contains(&(spot.0 + 1, spot.1 + 1)) {
-        return (spot.0 + 1, spot.1 + 1);
+// where a grain from the source comes to rest
+fn drop_sand(cave: &Cave) -> Point {
+    let mut spot = cave.source;
+
+    loop {
+        let next = [
+            Vector::DOWN,
+            Vector::DOWN + Vector::LEFT,
+            Vector::DOWN + Vector::RIGHT,
+        ]
+        .into_iter()
+        .map(|step| spot + step)
+        .find(|p| cave.grid.get(*p) == Some(&'.'));
+
+        match next {
+            Some(next) => spot = next,
+            None => return spot,
+        }
     }
-    return spot;
 }
 
-fn rock_set(contents: &str) -> (HashSet<(usize, usize)>, usize, usize, usize) {
-    let rock_lines: Vec<Vec<(usize, usize)>> = contents
-        .lines()
-        .map(|l| {
-            l.split(" -> ")
-                .map(|c| {
-                    c.split(",")
-                        .map(|num| num.parse::<usize>().unwrap())
-                        .collect_tuple()
-                        .unwrap()
-                })
-                .collect()
-        })
-        .collect();
-
-    let (max_i, max_j, min_j) = grid_dims(&rock_lines);
-
-    let mut rocks: HashSet<(usize, usize)> = HashSet::new();
-
-    for rl in &rock_lines {
-        for line in rl.windows(2) {
-            if line[0].0 == line[1].0 {
-                // vertical line
-                let start = line[0].1.min(line[1].1);
-                let fin = line[0].1.max(line[1].1);
-                for i in start..fin + 1 {
-                    rocks.insert((i, line[0].0));
-                }
-            } else if line[0].1 == line[1].1 {
-                // horizontal line
-                let start = line[0].0.min(line[1].0);
-                let fin = line[0].0.max(line[1].0);
-                for j in start..fin + 1 {
-                    rocks.insert((line[0].1, j));
+fn build_cave(contents: &str) -> Result<Cave, AocError> {
+    let mut rock_lines: Vec<Vec<Point>> = Vec::new();
+    for line in input_lines(DAY, contents) {
+        let mut rock_line: Vec<Point> = Vec::new();
+        for c in line.text.split(" -> ") {
+            let corner = match c.split_once(',') {
+                Some((x, y)) => Point::new(line.parse_num(x)?, line.parse_num(y)?),
+                None => return Err(line.error(c, "rock corner must look like x,y")),
+            };
+
+            if let Some(prev) = rock_line.last() {
+                let gap = corner - *prev;
+                if gap.dx != 0 && gap.dy != 0 {
+                    return Err(line.error(c, "rock paths must be horizontal or vertical"));
                 }
             }
+            if corner.x < 0 || corner.y < 0 {
+                return Err(line.error(c, "rock corner must be below and around the source"));
+            }
+            rock_line.push(corner);
         }
+        rock_lines.push(rock_line);
     }
 
-    return (rocks, max_i, max_j, min_j);
-}
+    let corners = rock_lines.iter().flatten();
+    let max_y = corners.clone().map(|p| p.y).max().unwrap_or(0);
+    let floor = max_y + 2;
+    let min_x = corners
+        .clone()
+        .map(|p| p.x)
+        .min()
+        .unwrap_or(500)
+        .min(500 - floor);
+    let max_x = corners.map(|p| p.x).max().unwrap_or(500).max(500 + floor);
+
+    // shift everything so the leftmost column the sand can reach is x = 0
+    let offset = Vector::new(-min_x, 0);
+    let width = (max_x - min_x + 1) as usize;
+    let mut grid = Grid::new(width, floor as usize + 1, '.');
 
-fn grid_dims(rock_lines: &Vec<Vec<(usize, usize)>>) -> (usize, usize, usize) {
-    let mut max_i = 0;
-    let mut max_j = 500;
-    let mut min_j = 500;
-    for rl in rock_lines {
-        for chunk in rl {
-            if chunk.1 > max_i {
-                max_i = chunk.1;
-            }
-            if chunk.0 > max_j {
-                max_j = chunk.0;
-            }
-            if chunk.0 < min_j {
-                min_j = chunk.0;
+    for rl in &rock_lines {
+        for line in rl.windows(2) {
+            let (start, fin) = (line[0] + offset, line[1] + offset);
+            let step = (fin - start).signum();
+            let mut spot = start;
+            grid[spot] = '#';
+            while spot != fin {
+                spot += step;
+                grid[spot] = '#';
             }
         }
+        if let [corner] = rl[..] {
+            grid[corner + offset] = '#';
+        }
+    }
+
+    for x in 0..width {
+        grid[Point::new(x as i64, floor)] = '#';
     }
-    (max_i, max_j, min_j)
+
+    Ok(Cave {
+        grid,
+        source: Point::new(500, 0) + offset,
+        max_y,
+    })
 }
 
 #[cfg(test)]
@@ -195,12 +169,12 @@ mod tests {
 503,4 -> 502,4 -> 502,9 -> 494,9";
         let p = 1;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day14.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return 1;
+            Answer::Int(1)
         });
 
-        assert_eq!(24, result);
+        assert_eq!(Answer::Int(24), result);
     }
 
     #[test]
@@ -209,11 +183,47 @@ mod tests {
 503,4 -> 502,4 -> 502,9 -> 494,9";
         let p = 2;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
+        let result = Day14.solve(input, p).unwrap_or_else(|err| {
             println!("failed to unwrap test result, {err}");
-            return 1;
+            Answer::Int(1)
         });
 
-        assert_eq!(93, result);
+        assert_eq!(Answer::Int(93), result);
+    }
+
+    #[test]
+    fn blocked_source() {
+        // the first grain rests on the source and nothing more can fall
+        let result = Day14.solve("499,1 -> 501,1", 1).unwrap_or_else(|err| {
+            println!("failed to unwrap test result, {err}");
+            Answer::Int(-1)
+        });
+
+        assert_eq!(Answer::Int(1), result);
+    }
+
+    #[test]
+    fn bad_corner() {
+        let input = "498,4 -> 498,6 -> 496,6
+503,4 -> 502 -> 502,9 -> 494,9";
+
+        let err = Day14.solve(input, 1).unwrap_err();
+
+        assert_eq!(
+            "day 14, line 2, column 10: rock corner must look like x,y (found \"502\")",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn diagonal_path() {
+        let input = "498,4 -> 498,6 -> 496,8";
+
+        let err = Day14.solve(input, 2).unwrap_err();
+
+        assert_eq!(
+            "day 14, line 1, column 19: rock paths must be horizontal or vertical (found \"496,8\")",
+            err.to_string()
+        );
     }
 }
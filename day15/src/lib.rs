@@ -1,189 +1,176 @@
 use std::collections::HashSet;
 use std::error::Error;
-use std::fs;
 
-pub struct Config {
-    file_path: String,
-    problem_number: i128,
-}
+pub use aoc_common::Config;
+use aoc_common::{input_lines, Answer, AocError, InputLine, Point, Solution};
 
-impl Config {
-    pub fn build(args: &[String]) -> Result<Config, &'static str> {
-        if args.len() < 3 {
-            return Err("must provide input file_path and which problem to solve");
-        }
-        let file_path = args[1].clone();
-        let problem_number = args[2].clone().parse::<i128>().unwrap_or_default();
-
-        Ok(Config {
-            file_path,
-            problem_number,
-        })
-    }
-}
+const DAY: usize = 15;
 
 #[derive(Debug, Default, Clone, Copy)]
-struct Sensor {
-    coords: Coords,
+pub struct Sensor {
+    coords: Point,
     nearest_beacon: Beacon,
-    md: i128,
+    md: i64,
 }
 
 #[derive(Debug, Default, Clone, Copy)]
 struct Beacon {
-    coords: Coords,
+    coords: Point,
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
-struct Coords {
-    x: i128,
-    y: i128,
+pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
+    aoc_common::run(DAY, &Day15::default(), config)
 }
 
-pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(config.file_path)?;
-    let result = solve(&contents, config.problem_number)?;
-    println!("result -> {result}");
-    Ok(())
+pub struct Day15 {
+    pub row: i64,
+    pub limit: i64,
 }
 
-fn solve(contents: &str, problem_number: i128) -> Result<i128, Box<dyn Error>> {
-    if problem_number == 1 {
-        let pos_no_beacon = positions_without_beacon(contents);
-        return Ok(pos_no_beacon);
-    } else {
-        return tuning_freq(contents);
+impl Default for Day15 {
+    fn default() -> Self {
+        Day15 {
+            row: 2000000,
+            limit: 4000000,
+        }
     }
 }
 
-fn tuning_freq(contents: &str) -> Result<i128, Box<dyn Error>> {
-    let sensors: Vec<Sensor> = parse_sensors(contents);
-    let s: HashSet<Coords> = sensors.iter().map(|a| a.coords).collect();
-    let b: HashSet<Coords> = sensors.iter().map(|a| a.nearest_beacon.coords).collect();
+impl Solution for Day15 {
+    type Input = Vec<Sensor>;
+
+    fn parse(&self, contents: &str) -> Result<Self::Input, AocError> {
+        parse_sensors(contents)
+    }
 
-    let mut to_check: HashSet<Coords> = HashSet::new();
+    fn part1(&self, input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(positions_without_beacon(input, self.row).into())
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(tuning_freq(input, self.limit)?.into())
+    }
+}
 
-    for (i, sensor) in sensors.iter().enumerate() {
-        dbg!(i);
+// the distress beacon has to sit just outside some sensor's range, so only the
+// border of each sensor's diamond needs checking
+fn tuning_freq(sensors: &[Sensor], limit: i64) -> Result<i128, AocError> {
+    let s: HashSet<Point> = sensors.iter().map(|a| a.coords).collect();
+    let b: HashSet<Point> = sensors.iter().map(|a| a.nearest_beacon.coords).collect();
+
+    let is_distress_beacon = |pt: Point| {
+        if s.contains(&pt) || b.contains(&pt) || !(0..=limit).contains(&pt.x) {
+            return false;
+        }
+
+        // point within a sensor's range
+        !sensors.iter().any(|s| pt.manhattan(s.coords) <= s.md)
+    };
+
+    for sensor in sensors {
         let border = sensor.md + 1;
         let top = sensor.coords.y - border;
         let bottom = sensor.coords.y + border;
 
         for y in top..bottom + 1 {
-            if y < 0 || y > 4000000 {
+            if !(0..=limit).contains(&y) {
                 continue;
             }
 
             let wiggle = border - (y - sensor.coords.y).abs();
-            let x_right = sensor.coords.x + wiggle;
-            let x_left = sensor.coords.x - wiggle;
-
-            if !(x_left < 0 || x_left > 4000000) {
-                to_check.insert(Coords { x: x_left, y: y });
-            }
-
-            if !(x_right < 0 || x_right > 4000000) {
-                to_check.insert(Coords { x: x_right, y: y });
+            for x in [sensor.coords.x - wiggle, sensor.coords.x + wiggle] {
+                if is_distress_beacon(Point::new(x, y)) {
+                    return Ok(x as i128 * 4000000 + y as i128);
+                }
             }
         }
     }
 
-    dbg!(to_check.len());
-
-    for (j, pt) in to_check.iter().enumerate() {
-        if s.contains(&pt)
-            || b.contains(&pt)
-            || pt.y < 0
-            || pt.x < 0
-            || pt.y > 4000000
-            || pt.x > 4000000
-        {
-            continue;
-        }
-
-        let mut in_range = false;
-        for s in &sensors {
-            if manhattan_distance(*pt, s.coords) <= s.md {
-                // point within a sensor's range
-                in_range = true;
-                break;
-            }
-        }
-        if !in_range {
-            return Ok(pt.x * 4000000 + pt.y);
-        }
-    }
-
-    Ok(0)
+    Err(AocError::unsolvable(
+        DAY,
+        "no spot left for the distress beacon",
+    ))
 }
 
-fn positions_without_beacon(contents: &str) -> i128 {
-    let sensors: Vec<Sensor> = parse_sensors(contents);
-
-    let s: HashSet<Coords> = sensors.iter().map(|a| a.coords).collect();
-    let b: HashSet<Coords> = sensors.iter().map(|a| a.nearest_beacon.coords).collect();
+fn positions_without_beacon(sensors: &[Sensor], y: i64) -> i64 {
+    let s: HashSet<Point> = sensors.iter().map(|a| a.coords).collect();
+    let b: HashSet<Point> = sensors.iter().map(|a| a.nearest_beacon.coords).collect();
 
-    let max_md = sensors.iter().map(|s| s.md).max().unwrap();
+    // parse_sensors guarantees at least one sensor
+    let max_md = sensors.iter().map(|s| s.md).max().unwrap_or_default();
     let min_x = sensors
         .iter()
         .map(|a| a.coords.x.min(a.nearest_beacon.coords.x))
         .min()
-        .unwrap()
+        .unwrap_or_default()
         - max_md;
     let max_x = sensors
         .iter()
         .map(|a| a.coords.x.max(a.nearest_beacon.coords.x))
         .max()
-        .unwrap()
+        .unwrap_or_default()
         + max_md;
 
     let mut result = 0;
-    let y = 2000000;
     for x in min_x..max_x {
-        let c = Coords { x, y };
+        let c = Point::new(x, y);
         if s.contains(&c) || b.contains(&c) {
             continue;
         }
-        for s in &sensors {
-            if manhattan_distance(c, s.coords) <= s.md {
+        for s in sensors {
+            if c.manhattan(s.coords) <= s.md {
                 result += 1;
                 break;
             }
         }
     }
 
-    return result;
+    result
 }
 
-fn parse_sensors(contents: &str) -> Vec<Sensor> {
-    return contents
-        .lines()
-        .map(|l| {
-            let mut s = Sensor::default();
-
-            let l = l
-                .replace("Sensor at ", "")
-                .replace(": closest beacon is at", "");
-            let raw_coords: Vec<i128> = l
-                .split(&[',', '=', 'x', 'y', ' '][..])
-                .filter(|p| *p != "")
-                .map(|n| n.parse::<i128>().unwrap())
-                .collect();
-
-            s.coords.x = raw_coords[0];
-            s.coords.y = raw_coords[1];
-            s.nearest_beacon.coords.x = raw_coords[2];
-            s.nearest_beacon.coords.y = raw_coords[3];
-
-            s.md = manhattan_distance(s.coords, s.nearest_beacon.coords);
-
-            s
-        })
-        .collect();
+fn parse_sensors(contents: &str) -> Result<Vec<Sensor>, AocError> {
+    let mut sensors = Vec::new();
+
+    for line in input_lines(DAY, contents) {
+        let (sensor, beacon) = match line
+            .text
+            .strip_prefix("Sensor at ")
+            .and_then(|l| l.split_once(": closest beacon is at "))
+        {
+            Some(parts) => parts,
+            None => {
+                return Err(line.error(
+                    line.text,
+                    "expected Sensor at x=N, y=N: closest beacon is at x=N, y=N",
+                ))
+            }
+        };
+
+        let coords = parse_coords(line, sensor)?;
+        let beacon = parse_coords(line, beacon)?;
+
+        sensors.push(Sensor {
+            coords,
+            nearest_beacon: Beacon { coords: beacon },
+            md: coords.manhattan(beacon),
+        });
+    }
+
+    if sensors.is_empty() {
+        return Err(AocError::unsolvable(DAY, "no sensors in input"));
+    }
+
+    Ok(sensors)
 }
 
-fn manhattan_distance(a: Coords, b: Coords) -> i128 {
-    (a.x - b.x).abs() + (a.y - b.y).abs()
+fn parse_coords(line: InputLine, raw: &str) -> Result<Point, AocError> {
+    match raw
+        .split_once(", ")
+        .and_then(|(x, y)| Some((x.strip_prefix("x=")?, y.strip_prefix("y=")?)))
+    {
+        Some((x, y)) => Ok(Point::new(line.parse_num(x)?, line.parse_num(y)?)),
+        None => Err(line.error(raw, "coordinates must look like x=N, y=N")),
+    }
 }
 
 #[cfg(test)]
@@ -209,12 +196,14 @@ Sensor at x=14, y=3: closest beacon is at x=15, y=3
 Sensor at x=20, y=1: closest beacon is at x=15, y=3";
         let p = 1;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
-            println!("failed to unwrap test result, {err}");
-            return 1;
-        });
+        let result = Day15 { row: 10, limit: 20 }
+            .solve(input, p)
+            .unwrap_or_else(|err| {
+                println!("failed to unwrap test result, {err}");
+                Answer::Int(1)
+            });
 
-        assert_eq!(26, result);
+        assert_eq!(Answer::Int(26), result);
     }
 
     #[test]
@@ -235,12 +224,14 @@ Sensor at x=14, y=3: closest beacon is at x=15, y=3
 Sensor at x=20, y=1: closest beacon is at x=15, y=3";
         let p = 2;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
-            println!("failed to unwrap test result, {err}");
-            return 1;
-        });
+        let result = Day15 { row: 10, limit: 20 }
+            .solve(input, p)
+            .unwrap_or_else(|err| {
+                println!("failed to unwrap test result, {err}");
+                Answer::BigInt(1)
+            });
 
-        assert_eq!(56000011, result);
+        assert_eq!(Answer::BigInt(56000011), result);
     }
 
     #[test]
@@ -248,11 +239,26 @@ Sensor at x=20, y=1: closest beacon is at x=15, y=3";
         let input = "Sensor at x=0, y=11: closest beacon is at x=2, y=11";
         let p = 2;
 
-        let result = solve(input, p).unwrap_or_else(|err| {
-            println!("failed to unwrap test result, {err}");
-            return 1;
-        });
+        let result = Day15 { row: 10, limit: 20 }
+            .solve(input, p)
+            .unwrap_or_else(|err| {
+                println!("failed to unwrap test result, {err}");
+                Answer::BigInt(1)
+            });
+
+        assert_eq!(Answer::BigInt(8), result);
+    }
+
+    #[test]
+    fn bad_coords() {
+        let input = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=1b: closest beacon is at x=10, y=16";
+
+        let err = Day15 { row: 10, limit: 20 }.solve(input, 1).unwrap_err();
 
-        assert_eq!(56000011, result);
+        assert_eq!(
+            "day 15, line 2, column 18: expected a number (found \"1b\")",
+            err.to_string()
+        );
     }
 }